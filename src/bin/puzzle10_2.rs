@@ -1,119 +1,80 @@
-use std::{
-    io::BufRead,
-    ops::{Mul, Range, RangeInclusive},
-};
-
-struct Circuit {
-    cycle: i32,
-    pixels: Vec<char>,
-    sprite: Sprite,
-}
-
-struct Sprite {
-    register: i32,
-    pos: RangeInclusive<usize>,
-}
-
-impl Sprite {
-    fn new() -> Self {
-        Sprite {
-            register: 1,
-            pos: 0..=1,
-        }
-    }
-
-    fn update(&mut self, steps: i32) {
-        // Updating the register
-        self.register += steps;
-
-        // Updating the position of the sprite
-        let mut start = self.register - 1;
-        let mut end = self.register + 1;
-
-        // Maintain the bounds of the sprite
-        if start < 0 {
-            start = 0;
-        }
-
-        if end > 39 {
-            end = 39
-        }
-
-        // Updating the internal pos field
-        self.pos = start as usize..=end as usize;
-    }
-}
+use std::io::Write;
 
-impl Circuit {
-    fn new() -> Self {
-        Circuit {
-            cycle: 0,
-            pixels: Vec::new(),
-            sprite: Sprite::new(),
-        }
-    }
+use aoc2022::{
+    cpu::{parse_program, Cpu, Instruction},
+    crt::{compile_image, draw_word, pixel_lit, read_letters, WIDTH},
+};
 
-    fn cycle_callback(&mut self) {
-        // Getting the current pixel position
-        let pixel_pos = self.cycle - 1;
+// Prints cycle, X and the executing instruction for every cycle
+const TRACE: bool = false;
 
-        // Getting the horizontal pixel position
-        let horizontal_pixel_pos = pixel_pos.rem_euclid(40) as usize;
+// Instead of the input, runs a program generated to draw this word and saves it
+// The first two pixels are always lit, so the word has to start with E, F or Z
+const GENERATE_WORD: Option<&str> = None;
+const GENERATED_PROGRAM: &str = "outputs/input10_generated.txt";
 
-        // Rendering the correct character based on whether overlapping or not
-        println!(
-            "{} against the range of {:?}",
-            &horizontal_pixel_pos, &self.sprite.pos
-        );
-        if self.sprite.pos.contains(&horizontal_pixel_pos) {
-            self.pixels.push('#');
-        } else {
-            self.pixels.push('.');
-        }
-    }
+fn generate(word: &str) -> (Vec<Instruction>, Vec<char>) {
+    let target =
+        draw_word(word).unwrap_or_else(|| panic!("Cannot draw \"{}\" with the CRT font", word));
+    let program = compile_image(&target).unwrap_or_else(|err| panic!("{}", err));
 
-    fn noop(&mut self) {
-        self.cycle += 1; // Starting a new cycle.
-        self.cycle_callback() // Callback during the current cycle.
+    let path = std::path::Path::new(GENERATED_PROGRAM);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(path).unwrap());
+    for instruction in program.iter() {
+        writeln!(writer, "{}", instruction).unwrap();
     }
 
-    fn add(&mut self, val: i32) {
-        self.noop(); // 1st cycle
-        self.noop(); // 2nd cycle
-        self.sprite.update(val);
-    }
-
-    fn print_display(&self) {
-        for chunk in self.pixels.chunks(40) {
-            println!("{:?}", chunk);
-        }
-    }
+    println!(
+        "Generated {} instructions drawing \"{}\" into {}",
+        program.len(),
+        word,
+        GENERATED_PROGRAM
+    );
+    (program, target)
 }
 
 fn main() {
     // Reading the file
     let file = std::fs::File::open("inputs/input10.txt").expect("Failed to open file.");
+    // let file = std::fs::File::open("inputs/input10_modified.txt").expect("Failed to open file.");
     let reader = std::io::BufReader::new(file);
-    let mut circuit = Circuit::new();
-
-    for rline in reader.lines() {
-        let line = rline.unwrap();
-        let pattern = regex::Regex::new("([A-z]{4}) ?(-*[0-9]*)").unwrap();
+    let (program, target) = match GENERATE_WORD {
+        Some(word) => {
+            let (program, target) = generate(word);
+            (program, Some(target))
+        }
+        None => (
+            parse_program(reader).unwrap_or_else(|err| panic!("Invalid program - {}", err)),
+            None,
+        ),
+    };
+
+    let mut cpu = Cpu::new(program);
+    cpu.trace = TRACE;
+
+    // The CRT draws one pixel per cycle
+    let mut pixels = Vec::new();
+    cpu.run(|tick| match pixel_lit(tick.cycle, tick.x) {
+        true => pixels.push('#'),
+        false => pixels.push('.'),
+    });
+
+    for row in pixels.chunks(WIDTH) {
+        println!("{}", row.iter().collect::<String>());
+    }
 
-        let captures = pattern.captures(&line).unwrap();
-        let op = captures.get(1).unwrap().as_str();
-        let possible_val = captures.get(2);
-        match op {
-            "noop" => {
-                circuit.noop();
-            }
-            "addx" => {
-                let val = possible_val.unwrap().as_str().parse::<i32>().unwrap();
-                circuit.add(val);
-            }
-            _ => panic!(),
+    // Checking the generated program against the image it was built from
+    if let Some(target) = target {
+        match pixels == target {
+            true => println!("Generated program draws the target image"),
+            false => println!("Generated program does NOT draw the target image"),
         }
     }
 
-    circuit.print_display();
+    // Reading the letters off the display
+    match read_letters(&pixels) {
+        Ok(letters) => println!("Result: {}", letters),
+        Err(unknown) => println!("{}", unknown),
+    }
 }
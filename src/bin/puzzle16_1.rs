@@ -4,10 +4,43 @@ use std::{
     result,
 };
 
+use aoc2022::search;
 use regex::{Match, Regex};
 
 const REGEX_PATTERN: &str = r"Valve ([A-Z]{2}) has flow rate=([0-9]+); tunnels? leads? to valves? ((?:[A-Z]{2})(?:,\s*[A-Z]{2})*)";
 
+// Prints the number of minutes needed to walk between the start and the valves worth opening
+const PRINT_DISTANCES: bool = false;
+
+// Walking distances from the start and every valve with a flow to each of the others
+fn print_distances(flowrates: &HashMap<String, u32>, neighbours: &HashMap<String, Vec<String>>) {
+    let mut valves = flowrates
+        .iter()
+        .filter(|(valve, flowrate)| **flowrate > 0 || valve.as_str() == "AA")
+        .map(|(valve, _)| valve.clone())
+        .collect::<Vec<_>>();
+    valves.sort();
+
+    let graph = |valve: &String| -> Vec<(String, u32)> {
+        neighbours[valve]
+            .iter()
+            .map(|next| (next.clone(), 1))
+            .collect()
+    };
+    for from in valves.iter() {
+        let result = search::bfs(&graph, [from.clone()]);
+        let distances = valves
+            .iter()
+            .filter(|to| *to != from)
+            .map(|to| match result.distance(to) {
+                Some(distance) => format!("{} {}", to, distance),
+                None => format!("{} -", to),
+            })
+            .collect::<Vec<_>>();
+        println!("{}: {}", from, distances.join(", "));
+    }
+}
+
 fn backtrack(
     valve: String,
     current_time: u32,
@@ -216,6 +249,10 @@ fn main() {
         neighbours.insert(valve, neighbour);
     }
 
+    if PRINT_DISTANCES {
+        print_distances(&flowrates, &neighbours);
+    }
+
     let mut opened = HashSet::new();
     let mut visited = HashSet::new();
     let result = backtrack(
@@ -1,4 +1,9 @@
-use std::{collections::HashSet, io::BufRead};
+use std::{
+    collections::{HashMap, HashSet},
+    io::{BufRead, Write},
+};
+
+use aoc2022::search;
 
 #[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
 enum ReferenceAxis {
@@ -58,6 +63,184 @@ impl Cube {
 
 const REGEX_PATTERN: &str = "([0-9]+),([0-9]+),([0-9]+)";
 
+// Writes the exposed surfaces as a 3D mesh - .obj for Wavefront OBJ, .stl for ASCII STL
+// e.g. Some("outputs/puzzle18.obj")
+const MESH_OUTPUT: Option<&str> = None;
+// Only export the surfaces that can be reached from outside the droplet
+const MESH_EXTERIOR_ONLY: bool = false;
+
+type Vertex = [i16; 3];
+
+impl Coord {
+    fn to_vertex(self) -> Vertex {
+        [self.x as i16, self.y as i16, self.z as i16]
+    }
+}
+
+impl Surface {
+    // Returns the index of the axis perpendicular to the surface, and its position along that axis
+    fn axis(&self) -> (usize, i16) {
+        match self.reference_axis {
+            ReferenceAxis::x(v) => (0, v as i16),
+            ReferenceAxis::y(v) => (1, v as i16),
+            ReferenceAxis::z(v) => (2, v as i16),
+        }
+    }
+
+    // The cubes on either side of the surface, ordered as (negative side, positive side)
+    fn adjacent_cubes(&self) -> (Vertex, Vertex) {
+        let (axis, position) = self.axis();
+        let (a, b) = (self.surface_coord.0 as i16, self.surface_coord.1 as i16);
+        let positive = match axis {
+            0 => [position, a, b],
+            1 => [a, position, b],
+            _ => [a, b, position],
+        };
+        let mut negative = positive;
+        negative[axis] -= 1;
+        (negative, positive)
+    }
+
+    // Corners of the surface, wound counter-clockwise when viewed from the side the normal points to
+    fn corners(&self, outward_positive: bool) -> (Vertex, [Vertex; 4]) {
+        let (axis, _) = self.axis();
+        // The surface lies on the lowest corner of the cube on its positive side
+        let (_, origin) = self.adjacent_cubes();
+
+        // Taking the remaining two axes in cyclic order, so that u x w points along the positive axis
+        let (u, w) = ((axis + 1) % 3, (axis + 2) % 3);
+        let step = |mut vertex: Vertex, along: usize| {
+            vertex[along] += 1;
+            vertex
+        };
+        let mut corners = [
+            origin,
+            step(origin, u),
+            step(step(origin, u), w),
+            step(origin, w),
+        ];
+
+        let mut normal = [0; 3];
+        match outward_positive {
+            true => normal[axis] = 1,
+            false => {
+                normal[axis] = -1;
+                corners.reverse();
+            }
+        }
+        (normal, corners)
+    }
+}
+
+// Flood filling the space around the droplet, returning the exterior air blocks
+fn find_exterior(cubes: &HashSet<Vertex>) -> HashSet<Vertex> {
+    let mut lower = [i16::MAX; 3];
+    let mut upper = [i16::MIN; 3];
+    for cube in cubes.iter() {
+        for i in 0..3 {
+            lower[i] = lower[i].min(cube[i] - 1);
+            upper[i] = upper[i].max(cube[i] + 1);
+        }
+    }
+
+    // BFS from the padded corner, only through the air within the padded bounding box
+    let neighbours = |c: &Vertex| {
+        let mut result = Vec::new();
+        for i in 0..3 {
+            for delta in [-1, 1] {
+                let mut nei = *c;
+                nei[i] += delta;
+                if (0..3).all(|i| nei[i] >= lower[i] && nei[i] <= upper[i]) && !cubes.contains(&nei)
+                {
+                    result.push((nei, 1));
+                }
+            }
+        }
+        result
+    };
+    search::bfs(&neighbours, [lower])
+        .distances
+        .into_keys()
+        .collect()
+}
+
+fn write_mesh(
+    path: &str,
+    surfaces: &HashSet<Surface>,
+    cubes: &HashSet<Vertex>,
+    exterior_only: bool,
+) -> std::io::Result<usize> {
+    let exterior = match exterior_only {
+        true => Some(find_exterior(cubes)),
+        false => None,
+    };
+
+    // Orienting each surface away from the cube it belongs to
+    let mut faces = Vec::new();
+    for surface in surfaces.iter() {
+        let (negative, positive) = surface.adjacent_cubes();
+        let outward_positive = cubes.contains(&negative);
+        let outside = if outward_positive { positive } else { negative };
+        if let Some(exterior) = &exterior {
+            if !exterior.contains(&outside) {
+                continue;
+            }
+        }
+        faces.push(surface.corners(outward_positive));
+    }
+
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+
+    if path.ends_with(".stl") {
+        writeln!(writer, "solid droplet")?;
+        for (normal, c) in faces.iter() {
+            for triangle in [[c[0], c[1], c[2]], [c[0], c[2], c[3]]] {
+                writeln!(
+                    writer,
+                    "facet normal {} {} {}",
+                    normal[0], normal[1], normal[2]
+                )?;
+                writeln!(writer, "  outer loop")?;
+                for v in triangle {
+                    writeln!(writer, "    vertex {} {} {}", v[0], v[1], v[2])?;
+                }
+                writeln!(writer, "  endloop")?;
+                writeln!(writer, "endfacet")?;
+            }
+        }
+        writeln!(writer, "endsolid droplet")?;
+    } else {
+        // Sharing the vertices between the faces - OBJ indices start from 1
+        let mut vertex_indices: HashMap<Vertex, usize> = HashMap::new();
+        let mut face_lines = Vec::new();
+        writeln!(writer, "o droplet")?;
+        for (_, corners) in faces.iter() {
+            let mut indices = Vec::new();
+            for v in corners {
+                let next_index = vertex_indices.len() + 1;
+                let index = *vertex_indices.entry(*v).or_insert_with(|| {
+                    face_lines.push(format!("v {} {} {}", v[0], v[1], v[2]));
+                    next_index
+                });
+                indices.push(index.to_string());
+            }
+            face_lines.push(format!("f {}", indices.join(" ")));
+        }
+        // Vertices must be declared before they are referenced
+        for line in face_lines.iter().filter(|l| l.starts_with("v ")) {
+            writeln!(writer, "{}", line)?;
+        }
+        for line in face_lines.iter().filter(|l| l.starts_with("f ")) {
+            writeln!(writer, "{}", line)?;
+        }
+    }
+
+    Ok(faces.len())
+}
+
 fn main() {
     // Reading inputs
     let file = std::fs::File::open("inputs/alt/input18.txt").expect("Failed to open file.");
@@ -65,6 +248,7 @@ fn main() {
 
     let mut existing_surfaces = HashSet::new();
     let mut joined_surfaces = HashSet::new();
+    let mut cubes = HashSet::new();
 
     // Compiling the regex pattern
     let pattern = regex::Regex::new(REGEX_PATTERN).unwrap();
@@ -78,6 +262,7 @@ fn main() {
         let z: u8 = matched.get(3).unwrap().as_str().parse().unwrap();
 
         let cube = Cube(Coord { x: x, y: y, z: z });
+        cubes.insert(cube.0.to_vertex());
         let cube_surfaces = cube.generate_surfaces();
 
         for cs in cube_surfaces {
@@ -93,4 +278,11 @@ fn main() {
         }
     }
     println!("Result: {}", existing_surfaces.len());
+
+    // Exporting the surfaces for viewing
+    if let Some(path) = MESH_OUTPUT {
+        let face_count = write_mesh(path, &existing_surfaces, &cubes, MESH_EXTERIOR_ONLY)
+            .expect("Failed to write mesh.");
+        println!("Exported {} faces to {}", face_count, path);
+    }
 }
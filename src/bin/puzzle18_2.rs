@@ -1,7 +1,8 @@
 use std::{collections::HashSet, io::BufRead};
 
-const REGEX_PATTERN: &str = "([0-9]+),([0-9]+),([0-9]+)";
-const GRID_UPPER_LIMIT: usize = 25;
+use aoc2022::search;
+
+const REGEX_PATTERN: &str = "(-?[0-9]+),(-?[0-9]+),(-?[0-9]+)";
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 enum BlockType {
@@ -11,99 +12,43 @@ enum BlockType {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Coord((u16, u16, u16));
-
-impl From<Coord> for usize {
-    fn from(value: Coord) -> Self {
-        let (x, y, z) = (value.0 .0, value.0 .1, value.0 .2);
-        let result = (x * GRID_UPPER_LIMIT as u16 * GRID_UPPER_LIMIT as u16)
-            + (y * GRID_UPPER_LIMIT as u16)
-            + z;
-        result as usize
-    }
-}
-
-impl From<usize> for Coord {
-    fn from(value: usize) -> Self {
-        let x = value.div_euclid(GRID_UPPER_LIMIT * GRID_UPPER_LIMIT);
-        let y = (value - (x * GRID_UPPER_LIMIT * GRID_UPPER_LIMIT)).div_euclid(GRID_UPPER_LIMIT);
-        let z = (value - (x * GRID_UPPER_LIMIT * GRID_UPPER_LIMIT)).rem_euclid(GRID_UPPER_LIMIT);
-        let result = Coord((x as u16, y as u16, z as u16));
-        result
-    }
-}
+struct Coord((i32, i32, i32));
 
 impl Coord {
-    fn get_left_block(&self) -> Option<Self> {
+    fn get_left_block(&self) -> Self {
         let (x, y, z) = (self.0 .0, self.0 .1, self.0 .2);
-        if x == 0 {
-            None
-        } else {
-            Some(Coord((x - 1, y, z)))
-        }
+        Coord((x - 1, y, z))
     }
-    fn get_right_block(&self) -> Option<Self> {
+    fn get_right_block(&self) -> Self {
         let (x, y, z) = (self.0 .0, self.0 .1, self.0 .2);
-        if x + 1 == GRID_UPPER_LIMIT as u16 {
-            None
-        } else {
-            Some(Coord((x + 1, y, z)))
-        }
+        Coord((x + 1, y, z))
     }
-    fn get_lower_block(&self) -> Option<Self> {
+    fn get_lower_block(&self) -> Self {
         let (x, y, z) = (self.0 .0, self.0 .1, self.0 .2);
-        if y == 0 {
-            None
-        } else {
-            Some(Coord((x, y - 1, z)))
-        }
+        Coord((x, y - 1, z))
     }
-    fn get_upper_block(&self) -> Option<Self> {
+    fn get_upper_block(&self) -> Self {
         let (x, y, z) = (self.0 .0, self.0 .1, self.0 .2);
-        if y + 1 == GRID_UPPER_LIMIT as u16 {
-            None
-        } else {
-            Some(Coord((x, y + 1, z)))
-        }
+        Coord((x, y + 1, z))
     }
-    fn get_behind_block(&self) -> Option<Self> {
+    fn get_behind_block(&self) -> Self {
         let (x, y, z) = (self.0 .0, self.0 .1, self.0 .2);
-        if z == 0 {
-            None
-        } else {
-            Some(Coord((x, y, z - 1)))
-        }
+        Coord((x, y, z - 1))
     }
-    fn get_front_block(&self) -> Option<Self> {
+    fn get_front_block(&self) -> Self {
         let (x, y, z) = (self.0 .0, self.0 .1, self.0 .2);
-        if z + 1 == GRID_UPPER_LIMIT as u16 {
-            None
-        } else {
-            Some(Coord((x, y, z + 1)))
-        }
+        Coord((x, y, z + 1))
     }
 
     fn get_surrounding_rocks(&self) -> Vec<Coord> {
-        let mut result = Vec::new();
-        if let Some(c) = self.get_left_block() {
-            result.push(c);
-        }
-        if let Some(c) = self.get_right_block() {
-            result.push(c);
-        }
-        if let Some(c) = self.get_upper_block() {
-            result.push(c);
-        }
-        if let Some(c) = self.get_lower_block() {
-            result.push(c);
-        }
-        if let Some(c) = self.get_front_block() {
-            result.push(c);
-        }
-        if let Some(c) = self.get_behind_block() {
-            result.push(c);
-        }
-        result
+        vec![
+            self.get_left_block(),
+            self.get_right_block(),
+            self.get_upper_block(),
+            self.get_lower_block(),
+            self.get_front_block(),
+            self.get_behind_block(),
+        ]
     }
 
     fn get_surrounding_rock_count(&self, grid: &mut Grid) -> u16 {
@@ -124,27 +69,174 @@ impl Coord {
             .sum();
         result
     }
+
+    fn get_surrounding_water_count(&self, grid: &mut Grid) -> u16 {
+        self.get_surrounding_rocks()
+            .into_iter()
+            .filter(|c| matches!(grid.get_mut_block(*c), Some(BlockType::Water)))
+            .count() as u16
+    }
+}
+
+// A connected group of air blocks that the water cannot reach
+struct AirPocket {
+    blocks: Vec<Coord>,
+    lower: Coord,
+    upper: Coord,
+    surface_area: u16,
+}
+
+impl AirPocket {
+    fn volume(&self) -> usize {
+        self.blocks.len()
+    }
+}
+
+// Total surface area as in part 1, worked out from the rocks alone - a face shared by two rocks is
+// added by the first and removed again by the second. Faces are keyed by their centres, at doubled
+// coordinates so that they stay integers.
+fn total_surface_area(rock_coords: &[Coord]) -> usize {
+    let mut surfaces = HashSet::new();
+    for Coord((x, y, z)) in rock_coords.iter() {
+        let (x, y, z) = (2 * x, 2 * y, 2 * z);
+        let faces = [
+            (x - 1, y, z),
+            (x + 1, y, z),
+            (x, y - 1, z),
+            (x, y + 1, z),
+            (x, y, z - 1),
+            (x, y, z + 1),
+        ];
+        for face in faces {
+            if !surfaces.remove(&face) {
+                surfaces.insert(face);
+            }
+        }
+    }
+    surfaces.len()
 }
 
-// NOTE: Maximum point in the input is 20, so a 25x25x25 grid should do the trick.
+// Grouping the remaining air blocks into connected pockets
+// Must be called after the water has filled the exterior of the droplet
+fn find_air_pockets(grid: &mut Grid) -> Vec<AirPocket> {
+    let mut pockets = Vec::new();
+    let mut processed = HashSet::new();
+
+    for i in 0..grid.get_grid_length() {
+        let start = grid.get_coord(i);
+        if processed.contains(&start) || grid.get_block(start) != Some(BlockType::Air) {
+            continue;
+        }
+
+        // BFS over the air blocks connected to the starting block
+        let connected = search::bfs(
+            &|c: &Coord| grid.get_neighbours_of_type(*c, BlockType::Air),
+            [start],
+        );
+
+        let mut pocket = AirPocket {
+            blocks: Vec::new(),
+            lower: start,
+            upper: start,
+            surface_area: 0,
+        };
+        for c in connected.distances.into_keys() {
+            let (x, y, z) = c.0;
+            let (lx, ly, lz) = pocket.lower.0;
+            let (ux, uy, uz) = pocket.upper.0;
+            pocket.lower = Coord((lx.min(x), ly.min(y), lz.min(z)));
+            pocket.upper = Coord((ux.max(x), uy.max(y), uz.max(z)));
+            pocket.surface_area += c.get_surrounding_rock_count(grid);
+            pocket.blocks.push(c);
+            processed.insert(c);
+        }
+
+        pockets.push(pocket);
+    }
+
+    pockets
+}
+
+// The grid spans the bounding box of the rock blocks, padded by a single block on every side.
+// The padding guarantees that the corners of the grid are always exterior air, so the water can flow around the entire droplet.
 struct Grid {
     _grid: Vec<BlockType>,
-    div: usize,
+    lower: Coord,
+    dims: (usize, usize, usize),
 }
 
 impl Grid {
-    fn new() -> Self {
-        let grid = vec![BlockType::Air; GRID_UPPER_LIMIT * GRID_UPPER_LIMIT * GRID_UPPER_LIMIT];
+    fn new(rock_coords: &[Coord]) -> Self {
+        let mut lower = (i32::MAX, i32::MAX, i32::MAX);
+        let mut upper = (i32::MIN, i32::MIN, i32::MIN);
+        for Coord((x, y, z)) in rock_coords.iter() {
+            lower = (lower.0.min(*x), lower.1.min(*y), lower.2.min(*z));
+            upper = (upper.0.max(*x), upper.1.max(*y), upper.2.max(*z));
+        }
+        if rock_coords.is_empty() {
+            lower = (0, 0, 0);
+            upper = (0, 0, 0);
+        }
+
+        // Adding the padding shell
+        let lower = (lower.0 - 1, lower.1 - 1, lower.2 - 1);
+        let upper = (upper.0 + 1, upper.1 + 1, upper.2 + 1);
+        let dims = (
+            (upper.0 - lower.0 + 1) as usize,
+            (upper.1 - lower.1 + 1) as usize,
+            (upper.2 - lower.2 + 1) as usize,
+        );
+
+        let grid = vec![BlockType::Air; dims.0 * dims.1 * dims.2];
         Grid {
             _grid: grid,
-            div: GRID_UPPER_LIMIT,
+            lower: Coord(lower),
+            dims,
+        }
+    }
+
+    // Maps a coordinate onto the index of the underlying vec, None if it lies outside of the grid
+    fn get_index(&self, coord: Coord) -> Option<usize> {
+        let (x, y, z) = coord.0;
+        let (lx, ly, lz) = self.lower.0;
+        let (dx, dy, dz) = (x - lx, y - ly, z - lz);
+        if dx < 0 || dy < 0 || dz < 0 {
+            return None;
         }
+        let (dx, dy, dz) = (dx as usize, dy as usize, dz as usize);
+        if dx >= self.dims.0 || dy >= self.dims.1 || dz >= self.dims.2 {
+            return None;
+        }
+        Some((dx * self.dims.1 * self.dims.2) + (dy * self.dims.2) + dz)
+    }
+
+    // Inverse of get_index
+    fn get_coord(&self, idx: usize) -> Coord {
+        let dx = idx / (self.dims.1 * self.dims.2);
+        let dy = (idx / self.dims.2) % self.dims.1;
+        let dz = idx % self.dims.2;
+        let (lx, ly, lz) = self.lower.0;
+        Coord((lx + dx as i32, ly + dy as i32, lz + dz as i32))
+    }
+
+    fn get_block(&self, coord: Coord) -> Option<BlockType> {
+        let arr_idx = self.get_index(coord)?;
+        self._grid.get(arr_idx).copied()
+    }
+
+    // Neighbouring blocks of the given type, each a single step away
+    fn get_neighbours_of_type(&self, coord: Coord, block_type: BlockType) -> Vec<(Coord, u32)> {
+        coord
+            .get_surrounding_rocks()
+            .into_iter()
+            .filter(|nei| self.get_block(*nei) == Some(block_type))
+            .map(|nei| (nei, 1))
+            .collect()
     }
 
     fn get_mut_block(&mut self, coord: Coord) -> Option<&mut BlockType> {
-        let arr_idx: usize = coord.into();
-        let result = self._grid.get_mut(arr_idx);
-        result
+        let arr_idx = self.get_index(coord)?;
+        self._grid.get_mut(arr_idx)
     }
 
     fn get_grid_length(&self) -> usize {
@@ -166,15 +258,15 @@ fn main() {
         let line = rline.unwrap();
         let matched = pattern.captures(&line).unwrap();
 
-        let x: u16 = matched.get(1).unwrap().as_str().parse().unwrap();
-        let y: u16 = matched.get(2).unwrap().as_str().parse().unwrap();
-        let z: u16 = matched.get(3).unwrap().as_str().parse().unwrap();
+        let x: i32 = matched.get(1).unwrap().as_str().parse().unwrap();
+        let y: i32 = matched.get(2).unwrap().as_str().parse().unwrap();
+        let z: i32 = matched.get(3).unwrap().as_str().parse().unwrap();
 
         rock_coords.push(Coord((x, y, z)));
     }
 
     // Spawning the grid
-    let mut grid = Grid::new();
+    let mut grid = Grid::new(&rock_coords);
 
     // Populating the grid with rock blocks
     for rc in rock_coords.iter() {
@@ -187,60 +279,50 @@ fn main() {
     }
 
     // Simulating water filling the blocks
-    // BFS method used for filling simulation
-    let mut processed = HashSet::new();
-    let mut neighbours = std::collections::VecDeque::new();
-    neighbours.push_back(Coord((0, 0, 0)));
+    // BFS method used for filling simulation, starting from the padded corner of the grid
+    let water = search::bfs(
+        &|c: &Coord| grid.get_neighbours_of_type(*c, BlockType::Air),
+        [grid.lower],
+    );
 
-    while let Some(c) = neighbours.pop_front() {
-        // Skip processing this block if it has already been processed
-        if processed.contains(&c) {
-            continue;
+    // Converting Air to Water
+    for c in water.distances.into_keys() {
+        if let Some(block) = grid.get_mut_block(c) {
+            *block = BlockType::Water;
         }
-
-        // Converting Air to Water
-        match grid.get_mut_block(c) {
-            Some(block) => match block {
-                BlockType::Air => {
-                    *block = BlockType::Water;
-                }
-                BlockType::Rock => {
-                    continue;
-                }
-                BlockType::Water => {}
-            },
-            None => unreachable!(),
-        }
-
-        // Marking the current block as processed
-        processed.insert(c);
-
-        // Adding the neighbours of the current block for processing
-        neighbours.extend(c.get_surrounding_rocks());
     }
 
-    // Getting the number of non-facing rock surfaces for each rock block
-    let mut surface_count = 0;
-    for rc in rock_coords.iter() {
-        let rock_surface_count = rc.get_surrounding_rock_count(&mut grid);
-        surface_count += 6 - rock_surface_count;
-    }
+    // This is the total surface area from part 1, including the surfaces facing the air pockets
+    let surface_count = total_surface_area(&rock_coords);
 
-    // Getting the number of rock facing surfaces for the remaining air blocks
-    for i in 0..grid.get_grid_length() {
-        match grid.get_mut_block(i.into()) {
-            Some(block) => match block {
-                BlockType::Air => {
-                    let coord: Coord = i.into();
-                    surface_count -= coord.get_surrounding_rock_count(&mut grid);
-                }
-                BlockType::Rock => {}
-                BlockType::Water => {}
-            },
-            None => unreachable!(),
-        }
+    // Getting the rock facing surfaces for each of the air pockets
+    let pockets = find_air_pockets(&mut grid);
+    for (i, pocket) in pockets.iter().enumerate() {
+        println!(
+            "Air pocket {}: Volume: {} - Bounding box: {:?} to {:?} - Surface area: {}",
+            i + 1,
+            pocket.volume(),
+            pocket.lower.0,
+            pocket.upper.0,
+            pocket.surface_area
+        );
     }
+    let pocket_surface_count: u16 = pockets.iter().map(|p| p.surface_area).sum();
+
+    // Consistency check - The rock surfaces facing the water and the ones facing the air pockets
+    // have to make up the total area found without the grid
+    let exterior_surface_count: u16 = rock_coords
+        .iter()
+        .map(|rc| rc.get_surrounding_water_count(&mut grid))
+        .sum();
+    assert_eq!(
+        surface_count,
+        (exterior_surface_count + pocket_surface_count) as usize,
+        "Total surface area should equal the exterior surface area plus the air pocket surfaces."
+    );
 
     // Printing the results
-    println!("Result: {}", surface_count);
+    println!("Total surface area: {}", surface_count);
+    println!("Air pockets: {}", pockets.len());
+    println!("Result: {}", exterior_surface_count);
 }
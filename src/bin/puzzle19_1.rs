@@ -1,141 +1,6 @@
-use std::{
-    collections::HashSet,
-    io::BufRead,
-    ops::{Add, Mul, Sub},
-};
+use std::{collections::HashSet, io::BufRead};
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-enum Robot {
-    Ore,
-    Clay,
-    Obsidian,
-    Geode,
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Resources {
-    ore: u16,
-    clay: u16,
-    obsidian: u16,
-    geode: u16,
-}
-
-impl Resources {
-    fn new(ore: u16, clay: u16, obsidian: u16, geode: u16) -> Self {
-        Resources {
-            ore: ore,
-            clay: clay,
-            obsidian: obsidian,
-            geode: geode,
-        }
-    }
-}
-
-impl Sub for Resources {
-    type Output = Option<Self>;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        if (self.ore < rhs.ore)
-            | (self.clay < rhs.clay)
-            | (self.obsidian < rhs.obsidian)
-            | (self.geode < rhs.geode)
-        {
-            return None;
-        }
-
-        Some(Resources {
-            ore: self.ore - rhs.ore,
-            clay: self.clay - rhs.clay,
-            obsidian: self.obsidian - rhs.obsidian,
-            geode: self.geode - rhs.geode,
-        })
-    }
-}
-
-impl Add for Resources {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Resources {
-            ore: self.ore + rhs.ore,
-            clay: self.clay + rhs.clay,
-            obsidian: self.obsidian + rhs.obsidian,
-            geode: self.geode + rhs.geode,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Robots {
-    ore: u16,
-    clay: u16,
-    obsidian: u16,
-    geode: u16,
-}
-
-impl Robots {
-    fn new(ore: u16, clay: u16, obsidian: u16, geode: u16) -> Self {
-        Robots {
-            ore: ore,
-            clay: clay,
-            obsidian: obsidian,
-            geode: geode,
-        }
-    }
-
-    fn mine_resources(&self, resources: &mut Resources) {
-        let amount_of_ore_mined = self.ore;
-        resources.ore += amount_of_ore_mined;
-        let amount_of_clay_mined = self.clay;
-        resources.clay += amount_of_clay_mined;
-        let amount_of_obsidian_mined = self.obsidian;
-        resources.obsidian += amount_of_obsidian_mined;
-        let amount_of_geode_mined = self.geode;
-        resources.geode += amount_of_geode_mined;
-    }
-}
-
-impl Sub for Robots {
-    type Output = Option<Self>;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        if (self.ore < rhs.ore)
-            | (self.clay < rhs.clay)
-            | (self.obsidian < rhs.obsidian)
-            | (self.geode < rhs.geode)
-        {
-            return None;
-        }
-
-        Some(Robots {
-            ore: self.ore - rhs.ore,
-            clay: self.clay - rhs.clay,
-            obsidian: self.obsidian - rhs.obsidian,
-            geode: self.geode - rhs.geode,
-        })
-    }
-}
-
-impl Add for Robots {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Robots {
-            ore: self.ore + rhs.ore,
-            clay: self.clay + rhs.clay,
-            obsidian: self.obsidian + rhs.obsidian,
-            geode: self.geode + rhs.geode,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
-struct Blueprint {
-    ore_bot: Resources,
-    clay_bot: Resources,
-    obs_bot: Resources,
-    geo_bot: Resources,
-}
+use aoc2022::factory::{print_schedule, Blueprint, Plan, Resources, Robot, Robots};
 
 fn projected_geodes_formula(mut current_production_rate: u16, mut remaining_time: u16) -> u16 {
     let mut total = 0;
@@ -150,6 +15,9 @@ fn projected_geodes_formula(mut current_production_rate: u16, mut remaining_time
     total
 }
 
+// Prints the minute-by-minute build order that achieves the maximum geodes for each blueprint
+const PRINT_SCHEDULE: bool = false;
+
 const REGEX_PATTERN: &str = "Blueprint ([0-9]+): Each ore robot costs ([0-9]+) ore. Each clay robot costs ([0-9]+) ore. Each obsidian robot costs ([0-9]+) ore and ([0-9]+) clay. Each geode robot costs ([0-9]+) ore and ([0-9]+) obsidian.";
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -161,7 +29,13 @@ struct State {
     robot_queue: Option<Robot>,
 }
 
-fn advance_state(mut state: State, seen_states: &mut HashSet<State>, max_geodes: &mut u16) {
+fn advance_state(
+    mut state: State,
+    seen_states: &mut HashSet<State>,
+    max_geodes: &mut u16,
+    plan: &mut Plan,
+    best_plan: &mut Plan,
+) {
     // Decreasing the remaining state time
     match state.time_remaining.checked_sub(1) {
         Some(res) => {
@@ -170,6 +44,7 @@ fn advance_state(mut state: State, seen_states: &mut HashSet<State>, max_geodes:
         None => {
             if state.resources.geode > *max_geodes {
                 *max_geodes = state.resources.geode;
+                best_plan.clone_from(plan);
             }
             return;
         }
@@ -215,34 +90,22 @@ fn advance_state(mut state: State, seen_states: &mut HashSet<State>, max_geodes:
     }
 
     // Selecting the possible robots that can be built
-    if let Some(updated_resources) = state.resources - state.blueprint.geo_bot {
-        let mut branched_state = state.clone();
-        branched_state.resources = updated_resources;
-        branched_state.robot_queue = Some(Robot::Geode);
-        advance_state(branched_state, seen_states, max_geodes);
-    }
-    if let Some(updated_resources) = state.resources - state.blueprint.obs_bot {
-        let mut branched_state = state.clone();
-        branched_state.resources = updated_resources;
-        branched_state.robot_queue = Some(Robot::Obsidian);
-        advance_state(branched_state, seen_states, max_geodes);
-    }
-    if let Some(updated_resources) = state.resources - state.blueprint.clay_bot {
-        let mut branched_state = state.clone();
-        branched_state.resources = updated_resources;
-        branched_state.robot_queue = Some(Robot::Clay);
-        advance_state(branched_state, seen_states, max_geodes);
-    }
-    if let Some(updated_resources) = state.resources - state.blueprint.ore_bot {
-        let mut branched_state = state.clone();
-        branched_state.resources = updated_resources;
-        branched_state.robot_queue = Some(Robot::Ore);
-        advance_state(branched_state, seen_states, max_geodes);
+    for robot in [Robot::Geode, Robot::Obsidian, Robot::Clay, Robot::Ore] {
+        if let Some(updated_resources) = state.resources - state.blueprint.cost(robot) {
+            let mut branched_state = state.clone();
+            branched_state.resources = updated_resources;
+            branched_state.robot_queue = Some(robot);
+            plan.push(Some(robot));
+            advance_state(branched_state, seen_states, max_geodes, plan, best_plan);
+            plan.pop();
+        }
     }
 
     // Also consider that a robot may not be be built
     state.robot_queue = None;
-    advance_state(state, seen_states, max_geodes);
+    plan.push(None);
+    advance_state(state, seen_states, max_geodes, plan, best_plan);
+    plan.pop();
 }
 
 fn main() {
@@ -291,7 +154,7 @@ fn main() {
 
         // Creating the state
         let state = State {
-            blueprint,
+            blueprint: blueprint.clone(),
             time_remaining: 24,
             robots: robots,
             resources: resources,
@@ -299,7 +162,25 @@ fn main() {
         };
 
         // Advancing the state
-        advance_state(state, &mut seen_states, &mut max_geodes);
+        let mut plan = Vec::new();
+        let mut best_plan = Vec::new();
+        advance_state(
+            state,
+            &mut seen_states,
+            &mut max_geodes,
+            &mut plan,
+            &mut best_plan,
+        );
+
+        // Explaining how the maximum was reached
+        if PRINT_SCHEDULE {
+            println!("Blueprint Id: {} - Build order:", blueprint_num);
+            let replayed_geodes = print_schedule(&blueprint, &best_plan, 24);
+            assert_eq!(
+                replayed_geodes, max_geodes,
+                "Replayed schedule does not match the search result."
+            );
+        }
 
         // Printing the results
         println!("Maximum geodes: {}", max_geodes);
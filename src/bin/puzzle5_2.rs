@@ -1,95 +1,34 @@
-use std::io::BufRead;
+use std::num::NonZeroUsize;
+
+use aoc2022::crane::{parse_input, Crane, Model};
+
+// Draws the stacks at the start, after every N moves and at the end, e.g. NonZeroUsize::new(10)
+const RENDER_INTERVAL: Option<NonZeroUsize> = None;
 
 fn main() {
     // Reading the file
     let file = std::fs::File::open("inputs/input5.txt").expect("Failed to open file.");
     let reader = std::io::BufReader::new(file);
+    let (stacks, moves) =
+        parse_input(reader).unwrap_or_else(|err| panic!("Invalid input - {}", err));
 
-    // Hardcoding the configuration
-    let mut arrangement = Vec::with_capacity(9);
-    arrangement.push(
-        "bgsc"
-            .chars()
-            .into_iter()
-            .map(|c| c.to_ascii_uppercase())
-            .collect::<Vec<_>>(),
-    );
-    arrangement.push(
-        "tmwhjnvg"
-            .chars()
-            .into_iter()
-            .map(|c| c.to_ascii_uppercase())
-            .collect::<Vec<_>>(),
-    );
-    arrangement.push(
-        "mqs"
-            .chars()
-            .into_iter()
-            .map(|c| c.to_ascii_uppercase())
-            .collect::<Vec<_>>(),
-    );
-    arrangement.push(
-        "bsltwnm"
-            .chars()
-            .into_iter()
-            .map(|c| c.to_ascii_uppercase())
-            .collect::<Vec<_>>(),
-    );
-    arrangement.push(
-        "jzftvgwp"
-            .chars()
-            .into_iter()
-            .map(|c| c.to_ascii_uppercase())
-            .collect::<Vec<_>>(),
-    );
-    arrangement.push(
-        "ctbgqhs"
-            .chars()
-            .into_iter()
-            .map(|c| c.to_ascii_uppercase())
-            .collect::<Vec<_>>(),
-    );
-    arrangement.push(
-        "tjpbw"
-            .chars()
-            .into_iter()
-            .map(|c| c.to_ascii_uppercase())
-            .collect::<Vec<_>>(),
-    );
-    arrangement.push(
-        "gdczftqm"
-            .chars()
-            .into_iter()
-            .map(|c| c.to_ascii_uppercase())
-            .collect::<Vec<_>>(),
-    );
-    arrangement.push(
-        "nshbpf"
-            .chars()
-            .into_iter()
-            .map(|c| c.to_ascii_uppercase())
-            .collect::<Vec<_>>(),
-    );
-
-    // Compiling the regex used for matching the pattern
-    let pattern = regex::Regex::new("move ([0-9]+) from ([0-9]+) to ([0-9]+)").unwrap();
+    let mut crane = Crane::new(stacks, Model::CrateMover9001);
+    if RENDER_INTERVAL.is_some() {
+        println!("Starting stacks:\n{}", crane.render());
+    }
 
-    for line in reader.lines().skip(10) {
-        let line = line.unwrap();
-        let captures = pattern.captures(&line).unwrap(); // Should always be able to capture the intended groups.
-        let (count, starting, ending) = (
-            captures.get(1).unwrap().as_str().parse::<usize>().unwrap(),
-            captures.get(2).unwrap().as_str().parse::<usize>().unwrap(),
-            captures.get(3).unwrap().as_str().parse::<usize>().unwrap(),
-        );
+    for (index, step) in moves.iter().enumerate() {
+        if let Err(err) = crane.apply(step) {
+            panic!("Move {} ({}) failed - {}", index + 1, step, err);
+        }
 
-        let start = &mut arrangement[starting - 1];
-        let start_length = start.len();
-        let mut grabbed_crates = start.split_off(start_length - count);
-        let _ = &mut arrangement[ending - 1].append(&mut grabbed_crates);
+        let count = index + 1;
+        if let Some(interval) = RENDER_INTERVAL {
+            if count % interval.get() == 0 || count == moves.len() {
+                println!("After move {} ({}):\n{}", count, step, crane.render());
+            }
+        }
     }
 
-    for q in arrangement {
-        println!("{:?}", q.last().unwrap());
-    }
+    println!("Result: {}", crane.top_crates());
 }
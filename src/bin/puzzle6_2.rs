@@ -1,29 +1,16 @@
-use std::{collections::HashSet, error::Error, io::Read};
+use std::error::Error;
+
+use aoc2022::signal::{find_markers, START_OF_MESSAGE, START_OF_PACKET};
 
 fn main() -> Result<(), Box<dyn Error>> {
-    // Reading the file
-    let mut file = std::fs::File::open("inputs/input6.txt").expect("Failed to open file.");
-    let mut signal = String::new();
-    file.read_to_string(&mut signal)?;
-    let chars = signal.chars().collect::<Vec<_>>();
-    let (mut i, mut j) = (0, 14);
-    while j < chars.len() {
-        let result = is_distinct(chars.get(i..j).unwrap());
-        if result == true {
-            println!("{:?}", chars.get(i..j));
-            println!("Result: {}", j);
-        }
-        i += 1;
-        j += 1;
-    }
+    // Reading the file - the signal is streamed, not loaded as a whole
+    let file = std::fs::File::open("inputs/input6.txt").expect("Failed to open file.");
+    let markers = find_markers(file, &[START_OF_PACKET, START_OF_MESSAGE])?;
+    let (packet, message) = (markers[0], markers[1]);
 
-    Ok(())
-}
+    println!("Start-of-packet marker: {:?}", packet);
+    println!("Start-of-message marker: {:?}", message);
+    println!("Result: {}", message.ok_or("No marker in the signal")?);
 
-pub fn is_distinct(arr: &[char]) -> bool {
-    let distinct = arr.iter().collect::<HashSet<_>>();
-    if distinct.len() < arr.len() {
-        return false;
-    }
-    true
+    Ok(())
 }
@@ -1,114 +1,20 @@
-use std::{
-    fs::read,
-    io::{BufRead, Read},
-};
+use aoc2022::forest::{parse_forest, Survey};
 
-fn is_visible(arr: &Vec<Vec<u32>>, val: u32, pos: (usize, usize), max_pos: (usize, usize)) -> bool {
-    let mut flag_visible: bool = false;
-
-    // Getting the current value
-    let (mut x, mut y) = pos;
-    let (x_max, y_max) = max_pos;
-    let tree = val;
-
-    // Edges
-    if (x == 0) | (x == x_max) | (y == 0) | (y == y_max) {
-        flag_visible = true;
-        return flag_visible;
-    }
-
-    // Traversing each direction
-
-    // Traversing top direction
-    (x, y) = pos;
-    while x > 0 {
-        x = x - 1;
-        let other = arr.get(x).unwrap().get(y).unwrap();
-        if other >= &tree {
-            break;
-        }
-        if x == 0 {
-            flag_visible = true;
-            return flag_visible;
-        }
-    }
-
-    // Traversing bottom direction
-    (x, y) = pos;
-    while x < x_max {
-        x = x + 1;
-        let other = arr.get(x).unwrap().get(y).unwrap();
-        if other >= &tree {
-            break;
-        }
-        if x == x_max {
-            flag_visible = true;
-            return flag_visible;
-        }
-    }
-
-    // Traversing left direction
-    (x, y) = pos;
-    while y > 0 {
-        y = y - 1;
-        let other = arr.get(x).unwrap().get(y).unwrap();
-        if other >= &tree {
-            break;
-        }
-        if y == 0 {
-            flag_visible = true;
-            return flag_visible;
-        }
-    }
-
-    // Traversing right direction
-    (x, y) = pos;
-    while y < y_max {
-        y = y + 1;
-        let other = arr.get(x).unwrap().get(y).unwrap();
-        if other >= &tree {
-            break;
-        }
-        if y == y_max {
-            flag_visible = true;
-            return flag_visible;
-        }
-    }
-
-    flag_visible
-}
+// Prints the heights of the visible trees, with the hidden ones as '.'
+const RENDER_VISIBILITY: bool = false;
 
 fn main() {
     // Reading the file
     let file = std::fs::File::open("inputs/input8.txt").expect("Failed to open file.");
     let reader = std::io::BufReader::new(file);
+    let matrix = parse_forest(reader);
+    let survey = Survey::new(&matrix);
+    let unblocked_count = survey.visible_count();
 
-    let mut matrix = Vec::new();
-
-    // Filling up the matrix
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let mut row = Vec::new();
-        line.chars().into_iter().for_each(|c| {
-            let int = c.to_digit(10).unwrap();
-            row.push(int)
-        });
-        matrix.push(row)
+    if RENDER_VISIBILITY {
+        println!("{}", survey.render_visibility(&matrix));
     }
 
-    // Iterating through each row
-    let mut unblocked_count: i32 = 0;
-    let i_max = matrix.len() - 1;
-    let j_max = matrix[0].len() - 1;
-
-    matrix.iter().enumerate().for_each(|(i, row)| {
-        row.into_iter().enumerate().for_each(|(j, val)| {
-            if is_visible(&matrix, *val, (i, j), (i_max, j_max)) {
-                unblocked_count += 1
-            }
-        })
-    });
-
     println!("Result: {}", unblocked_count);
 
     // let mut i = 0;
@@ -1,98 +1,38 @@
-use std::{collections::HashSet, io::BufRead};
+use aoc2022::rope::{parse_motions, Bounds, Rope, Verbosity};
 
-#[derive(Debug)]
-enum Direction {
-    U,
-    D,
-    L,
-    R,
-}
-
-type Position = (i32, i32);
-
-struct Head {
-    pos: Position,
-    prev_pos: Position,
-}
-
-struct Tail {
-    pos: Position,
-    visited: HashSet<Position>,
-}
-
-impl Head {
-    fn new() -> Self {
-        Head {
-            pos: (0, 0),
-            prev_pos: (0, 0),
-        }
-    }
-
-    fn move_dir(&mut self, direction: &Direction) {
-        self.prev_pos = self.pos; // Assigning the current position to the previous position prior to a move.
-        match direction {
-            Direction::U => self.pos.1 += 1,
-            Direction::D => self.pos.1 += -1,
-            Direction::L => self.pos.0 += -1,
-            Direction::R => self.pos.0 += 1,
-        }
-    }
-}
-
-impl Tail {
-    fn new() -> Self {
-        let mut tail = Tail {
-            pos: (0, 0),
-            visited: HashSet::new(),
-        };
-        tail.visited.insert((0, 0)); // Including the initial position
-        tail
-    }
+// Frames are meant for small inputs like input9_modified.txt, the visited map for the full input
+const VERBOSITY: Verbosity = Verbosity::Quiet;
 
-    fn follow_head(&mut self, head: &Head) {
-        // Checking positional delta
-        let (x_delta, y_delta) = (head.pos.0 - self.pos.0, head.pos.1 - self.pos.1);
-        if (x_delta.abs() <= 1) & (y_delta.abs() <= 1) {
-            return; // Tail is in contact with the head
-        } else {
-            self.pos = head.prev_pos; // Moving to the position previously occupied by the head.
-        }
-
-        // Updating the visited field
-        self.visited.insert(self.pos);
-    }
-}
+// Number of knots in the rope, including the head
+const KNOTS: usize = 2;
 
 fn main() {
     // Reading the file
     let file = std::fs::File::open("inputs/input9.txt").expect("Failed to open file.");
+    // let file = std::fs::File::open("inputs/input9_modified.txt").expect("Failed to open file.");
     let reader = std::io::BufReader::new(file);
+    let motions = parse_motions(reader);
 
-    let pattern = regex::Regex::new("([A-Z]{1}) ([0-9]+)").unwrap();
-    let mut head = Head::new();
-    let mut tail = Tail::new();
-    for rline in reader.lines() {
-        let line = rline.unwrap();
-        let groups = pattern.captures(&line).unwrap();
-        let direction = match groups.get(1).unwrap().as_str() {
-            "U" => Direction::U,
-            "D" => Direction::D,
-            "L" => Direction::L,
-            "R" => Direction::R,
-            _ => panic!(),
-        };
-        let step_count = groups.get(2).unwrap().as_str().parse::<i32>().unwrap();
+    let bounds = Bounds::of_motions(&motions);
+    let mut rope = Rope::new(KNOTS);
+    if VERBOSITY == Verbosity::Frames {
+        println!("== Initial State ==\n\n{}", rope.render_frame(&bounds));
+    }
 
-        // Completing the steps for this round
-        let mut i = 0;
-        while i < step_count {
-            // println!("Head moves: {:?}", &direction);
-            head.move_dir(&direction);
-            tail.follow_head(&head);
-            // println!("Head: {:?}, Tail: {:?}", &head.pos, &tail.pos);
-            i += 1;
+    for motion in motions.iter() {
+        if VERBOSITY == Verbosity::Frames {
+            println!("== {} ==\n", motion);
         }
+        rope.apply(motion, |rope| {
+            if VERBOSITY == Verbosity::Frames {
+                println!("{}", rope.render_frame(&bounds));
+            }
+        });
+    }
+
+    if VERBOSITY == Verbosity::VisitedMap {
+        println!("{}", rope.render_visited(KNOTS - 1));
     }
 
-    println!("Result: {}", &tail.visited.len());
+    println!("Result: {}", rope.tail_visited().len());
 }
@@ -0,0 +1,465 @@
+// Cave engine for puzzle 14 - Sand falling from a source onto lines of rock
+use std::{
+    collections::HashSet,
+    io::{BufRead, Write},
+};
+
+const AIR_COLOUR: [u8; 3] = [20, 20, 30];
+const ROCK_COLOUR: [u8; 3] = [120, 120, 120];
+const SAND_COLOUR: [u8; 3] = [230, 200, 90];
+const SOURCE_COLOUR: [u8; 3] = [220, 40, 40];
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Coords {
+    // Signed, since sand piling up on the floor can spread left past x = 0
+    pub x: i32,
+    pub y: u32,
+}
+
+impl Coords {
+    pub fn new(x: i32, y: u32) -> Self {
+        Coords { x, y }
+    }
+
+    // Parses "x,y", with the reason it failed otherwise
+    fn from_str(input: &str) -> Result<Self, String> {
+        let (x, y) = input
+            .split_once(',')
+            .ok_or_else(|| format!("\"{}\" is not of the form x,y", input))?;
+        let x: i32 = x
+            .trim()
+            .parse()
+            .map_err(|err| format!("bad x in \"{}\": {}", input, err))?;
+        let y: u32 = y
+            .trim()
+            .parse()
+            .map_err(|err| format!("bad y in \"{}\": {}", input, err))?;
+        Ok(Coords { x, y })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Rock {
+    loc: Coords,
+}
+
+impl Rock {
+    fn new(loc: Coords) -> Self {
+        Rock { loc }
+    }
+
+    fn construct(start: Coords, end: Coords) -> Vec<Rock> {
+        match start.x == end.x {
+            true => {
+                let (min, max) = match start.y < end.y {
+                    true => (start.y, end.y),
+                    false => (end.y, start.y),
+                };
+                (min..=max)
+                    .map(|y| Rock::new(Coords::new(start.x, y)))
+                    .collect()
+            }
+            false => {
+                let (min, max) = match start.x < end.x {
+                    true => (start.x, end.x),
+                    false => (end.x, start.x),
+                };
+                (min..=max)
+                    .map(|x| Rock::new(Coords::new(x, start.y)))
+                    .collect()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // 1-based line of the input where parsing failed
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Reads the rock paths, one path of "x,y -> x,y -> ..." per line
+pub fn parse_rocks(reader: impl BufRead) -> Result<Vec<Rock>, ParseError> {
+    let mut rocks = HashSet::new();
+    for (index, rline) in reader.lines().enumerate() {
+        let line = rline.unwrap();
+        let error = |message: String| ParseError {
+            line: index + 1,
+            message,
+        };
+
+        let corners = line
+            .split(" -> ")
+            .map(Coords::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(error)?;
+        for window in corners.windows(2) {
+            let (start, end) = (window[0], window[1]);
+            // Rock paths only run straight across or straight down
+            if start.x != end.x && start.y != end.y {
+                return Err(error(format!(
+                    "diagonal rock from {},{} to {},{}",
+                    start.x, start.y, end.x, end.y
+                )));
+            }
+            rocks.extend(Rock::construct(start, end));
+        }
+    }
+    Ok(rocks.into_iter().collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndCondition {
+    // Stops as soon as a grain falls below the lowest rock
+    Abyss,
+    // Stops once the source itself is covered by sand
+    SourceBlocked,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaveConfig {
+    pub source: Coords,
+    // Distance of the floor below the lowest rock, None if the cave is bottomless.
+    // A floor can only be used with EndCondition::SourceBlocked, as sand never reaches the abyss past it.
+    pub floor_offset: Option<u32>,
+    pub end_condition: EndCondition,
+}
+
+impl CaveConfig {
+    // Sand pours into the abyss below the rocks
+    pub fn part1() -> Self {
+        CaveConfig {
+            source: Coords::new(500, 0),
+            floor_offset: None,
+            end_condition: EndCondition::Abyss,
+        }
+    }
+
+    // Sand piles up on the floor until the source is blocked
+    pub fn part2() -> Self {
+        CaveConfig {
+            source: Coords::new(500, 0),
+            floor_offset: Some(2),
+            end_condition: EndCondition::SourceBlocked,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+    // The floor has to lie at least one row below the lowest rock
+    FloorAtRockLevel,
+    // Sand never reaches the abyss once there is a floor
+    AbyssThroughFloor,
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::FloorAtRockLevel => write!(f, "the floor must lie below the lowest rock"),
+            ConfigError::AbyssThroughFloor => {
+                write!(f, "sand cannot fall into the abyss through a floor")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropResult {
+    Rested(Coords),
+    Abyss,
+    Blocked,
+}
+
+// Single dense grid holding both the rocks and the sand.
+// Sand can spread at most one step sideways for each step downwards, so the grid only needs to span
+// the source +/- the depth of the cave, in addition to the rocks themselves.
+pub struct Cave {
+    tiles: Vec<Tile>,
+    min_x: i32,
+    width: u32,
+    rows: u32,
+    lowest_y: u32,
+    floor_y: Option<u32>,
+    config: CaveConfig,
+    path: Vec<Coords>,
+}
+
+impl Cave {
+    pub fn new(rocks: &[Rock], config: CaveConfig) -> Result<Self, ConfigError> {
+        let source = config.source;
+        let lowest_y = match rocks.iter().max_by_key(|rock| rock.loc.y) {
+            Some(rock) => rock.loc.y.max(source.y),
+            None => source.y,
+        };
+        match (config.floor_offset, config.end_condition) {
+            (Some(0), _) => return Err(ConfigError::FloorAtRockLevel),
+            (Some(_), EndCondition::Abyss) => return Err(ConfigError::AbyssThroughFloor),
+            _ => {}
+        }
+        let floor_y = config.floor_offset.map(|offset| lowest_y + offset);
+
+        // Without a floor, a single row below the lowest rock is enough to tell that a grain has fallen through
+        let rows = floor_y.unwrap_or(lowest_y + 2);
+        let depth = rows - source.y;
+        let min_x = rocks
+            .iter()
+            .map(|rock| rock.loc.x)
+            .chain([source.x - (depth + 1) as i32])
+            .min()
+            .unwrap_or(0);
+        let max_x = rocks
+            .iter()
+            .map(|rock| rock.loc.x)
+            .chain([source.x + (depth + 1) as i32])
+            .max()
+            .unwrap_or(0);
+        let width = (max_x - min_x + 1) as u32;
+
+        let mut cave = Cave {
+            tiles: vec![Tile::Air; (width * rows) as usize],
+            min_x,
+            width,
+            rows,
+            lowest_y,
+            floor_y,
+            config,
+            path: Vec::new(),
+        };
+        for rock in rocks.iter() {
+            cave.set(rock.loc, Tile::Rock);
+        }
+        if cave.get(source) == Tile::Air {
+            cave.path.push(source);
+        }
+        Ok(cave)
+    }
+
+    fn index(&self, loc: Coords) -> usize {
+        (loc.y * self.width + (loc.x - self.min_x) as u32) as usize
+    }
+
+    fn get(&self, loc: Coords) -> Tile {
+        self.tiles[self.index(loc)]
+    }
+
+    fn set(&mut self, loc: Coords, tile: Tile) {
+        let idx = self.index(loc);
+        self.tiles[idx] = tile;
+    }
+
+    // Drops a single grain of sand, resuming from the path taken by the previous grain.
+    // Every location on the path is still empty air, as each grain only ever settles at the end of the path.
+    pub fn drop_sand(&mut self) -> DropResult {
+        while let Some(&current) = self.path.last() {
+            // Checking to see if it has fallen below the lowest rock
+            // Without a floor, every following grain would take the same path into the abyss
+            if current.y > self.lowest_y
+                && (self.floor_y.is_none() || self.config.end_condition == EndCondition::Abyss)
+            {
+                return DropResult::Abyss;
+            }
+
+            // Checking to see if the particle has reached the floor
+            let next = match Some(current.y + 1) == self.floor_y {
+                true => None,
+                false => {
+                    // Attempting to move downwards, then downwards-left, then downwards-right
+                    let below = [
+                        Coords::new(current.x, current.y + 1),
+                        Coords::new(current.x - 1, current.y + 1),
+                        Coords::new(current.x + 1, current.y + 1),
+                    ];
+                    below.into_iter().find(|c| self.get(*c) == Tile::Air)
+                }
+            };
+            match next {
+                Some(next) => {
+                    self.path.push(next);
+                }
+                None => {
+                    // Blocked in moving from all directions - Particle comes to rest
+                    self.set(current, Tile::Sand);
+                    self.path.pop();
+                    return DropResult::Rested(current);
+                }
+            }
+        }
+        // The path is only emptied once the source itself is filled - Entrance is blocked
+        DropResult::Blocked
+    }
+
+    // Drops sand until the end condition is met, returning the number of grains at rest.
+    // The callback is invoked after every grain comes to rest, with the running count.
+    pub fn run(&mut self, mut on_rest: impl FnMut(&Cave, u32)) -> u32 {
+        let mut count = 0;
+        loop {
+            match self.drop_sand() {
+                DropResult::Rested(_) => {
+                    count += 1;
+                    on_rest(self, count);
+                }
+                DropResult::Abyss | DropResult::Blocked => return count,
+            }
+        }
+    }
+
+    fn tile_char(&self, loc: Coords) -> char {
+        match self.get(loc) {
+            Tile::Rock => '#',
+            Tile::Sand => 'o',
+            Tile::Air if loc == self.config.source => '+',
+            Tile::Air => '.',
+        }
+    }
+
+    // Renders the cave within the bounding box of the rocks, sand and source, as drawn in the puzzle text
+    pub fn render_ascii(&self) -> String {
+        let source = self.config.source;
+        let mut min_x = source.x;
+        let mut max_x = source.x;
+        let mut min_y = source.y;
+        let mut max_y = source.y;
+        for (i, tile) in self.tiles.iter().enumerate() {
+            if *tile == Tile::Air {
+                continue;
+            }
+            let x = self.min_x + (i as u32 % self.width) as i32;
+            let y = i as u32 / self.width;
+            min_x = min_x.min(x);
+            max_x = max_x.max(x);
+            min_y = min_y.min(y);
+            max_y = max_y.max(y);
+        }
+
+        let mut result = String::new();
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                result.push(self.tile_char(Coords::new(x, y)));
+            }
+            result.push('\n');
+        }
+        // The floor is infinite, so it is drawn across the full width of the bounding box
+        if let Some(floor_y) = self.floor_y {
+            for _ in (max_y + 1)..floor_y {
+                result.push_str(&".".repeat((max_x - min_x + 1) as usize));
+                result.push('\n');
+            }
+            result.push_str(&"#".repeat((max_x - min_x + 1) as usize));
+            result.push('\n');
+        }
+        result
+    }
+
+    // Writes the entire cave as a binary netpbm (PPM) image, one pixel per tile
+    pub fn write_ppm(&self, path: &str) -> std::io::Result<()> {
+        if let Some(parent) = std::path::Path::new(path).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let floor_rows = match self.floor_y {
+            Some(_) => 1,
+            None => 0,
+        };
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        write!(
+            writer,
+            "P6\n{} {}\n255\n",
+            self.width,
+            self.rows + floor_rows
+        )?;
+        for y in 0..self.rows {
+            for x in self.min_x..(self.min_x + self.width as i32) {
+                let colour = match self.tile_char(Coords::new(x, y)) {
+                    '#' => ROCK_COLOUR,
+                    'o' => SAND_COLOUR,
+                    '+' => SOURCE_COLOUR,
+                    _ => AIR_COLOUR,
+                };
+                writer.write_all(&colour)?;
+            }
+        }
+        // The floor lies just below the bottom of the grid
+        for _ in 0..(self.width * floor_rows) {
+            writer.write_all(&ROCK_COLOUR)?;
+        }
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+
+    #[test]
+    fn example_counts() {
+        let rocks = parse_rocks(EXAMPLE.as_bytes()).unwrap();
+        let mut cave = Cave::new(&rocks, CaveConfig::part1()).unwrap();
+        assert_eq!(cave.run(|_, _| {}), 24);
+        let mut cave = Cave::new(&rocks, CaveConfig::part2()).unwrap();
+        assert_eq!(cave.run(|_, _| {}), 93);
+    }
+
+    #[test]
+    fn sand_spreads_past_x_zero() {
+        let rocks = parse_rocks("0,5 -> 4,5\n".as_bytes()).unwrap();
+        let config = CaveConfig {
+            source: Coords::new(2, 0),
+            floor_offset: Some(2),
+            end_condition: EndCondition::SourceBlocked,
+        };
+        // The pile is a full triangle 7 rows deep, less the 5 rock tiles and the 3 tiles sheltered below them
+        let mut cave = Cave::new(&rocks, config).unwrap();
+        assert_eq!(cave.run(|_, _| {}), 49 - 5 - 3);
+    }
+
+    #[test]
+    fn bad_configs() {
+        let rocks = parse_rocks(EXAMPLE.as_bytes()).unwrap();
+        let abyss = CaveConfig {
+            floor_offset: Some(2),
+            ..CaveConfig::part1()
+        };
+        assert_eq!(
+            Cave::new(&rocks, abyss).err(),
+            Some(ConfigError::AbyssThroughFloor)
+        );
+        let floor = CaveConfig {
+            floor_offset: Some(0),
+            ..CaveConfig::part2()
+        };
+        assert_eq!(
+            Cave::new(&rocks, floor).err(),
+            Some(ConfigError::FloorAtRockLevel)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = parse_rocks("498,4 -> 498,6\n498,6 -> 49x,6\n".as_bytes()).unwrap_err();
+        assert_eq!(error.line, 2);
+        let error = parse_rocks("498,4 -> 498\n".as_bytes()).unwrap_err();
+        assert_eq!(error.line, 1);
+        let error = parse_rocks("498,4 -> 500,6\n".as_bytes()).unwrap_err();
+        assert_eq!(error.message, "diagonal rock from 498,4 to 500,6");
+    }
+}
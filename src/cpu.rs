@@ -0,0 +1,342 @@
+// CPU emulator for puzzle 10 - A single register machine driving the CRT
+use std::io::BufRead;
+
+// Everything the CPU knows about an instruction - adding an instruction only needs a new entry
+pub struct InstructionSpec {
+    pub name: &'static str,
+    // Number of cycles the instruction takes to complete
+    pub cycles: u32,
+    pub takes_operand: bool,
+    // New value of the X register, applied once the last cycle has completed
+    pub effect: fn(x: i32, operand: i32) -> i32,
+}
+
+pub const INSTRUCTIONS: &[InstructionSpec] = &[
+    InstructionSpec {
+        name: "noop",
+        cycles: 1,
+        takes_operand: false,
+        effect: |x, _| x,
+    },
+    InstructionSpec {
+        name: "addx",
+        cycles: 2,
+        takes_operand: true,
+        effect: |x, operand| x + operand,
+    },
+];
+
+#[derive(Clone, Copy)]
+pub struct Instruction {
+    pub spec: &'static InstructionSpec,
+    pub operand: i32,
+}
+
+impl Instruction {
+    pub fn new(name: &str, operand: Option<i32>) -> Option<Self> {
+        let spec = INSTRUCTIONS.iter().find(|spec| spec.name == name)?;
+        match (spec.takes_operand, operand) {
+            (true, Some(operand)) => Some(Instruction { spec, operand }),
+            (false, None) => Some(Instruction { spec, operand: 0 }),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Debug for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.spec.takes_operand {
+            true => write!(f, "{} {}", self.spec.name, self.operand),
+            false => write!(f, "{}", self.spec.name),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    // 1-based line of the program where parsing failed
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Parses a program, one instruction per line
+pub fn parse_program(reader: impl BufRead) -> Result<Vec<Instruction>, ParseError> {
+    let pattern = regex::Regex::new(r"^([a-z]+)(?: (-?[0-9]+))?$").unwrap();
+    let mut program = Vec::new();
+
+    for (index, rline) in reader.lines().enumerate() {
+        let line = rline.unwrap();
+        let error = |message: String| ParseError {
+            line: index + 1,
+            message,
+        };
+
+        let captures = pattern
+            .captures(line.trim())
+            .ok_or_else(|| error(format!("malformed instruction \"{}\"", line)))?;
+        let name = captures.get(1).unwrap().as_str();
+        let operand = captures
+            .get(2)
+            .map(|val| val.as_str().parse::<i32>())
+            .transpose()
+            .map_err(|err| error(format!("bad operand in \"{}\": {}", line, err)))?;
+
+        let instruction = Instruction::new(name, operand)
+            .ok_or_else(|| error(format!("unknown instruction \"{}\"", line)))?;
+        program.push(instruction);
+    }
+
+    Ok(program)
+}
+
+// State of the CPU during a single cycle
+#[derive(Debug, Clone, Copy)]
+pub struct Tick {
+    // 1-based cycle number
+    pub cycle: u32,
+    // Value of X during the cycle, before the current instruction completes
+    pub x: i32,
+    pub instruction: Instruction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Condition {
+    Cycle(u32),
+    // Cycles start, start + interval, start + 2 * interval, ...
+    EveryCycles { start: u32, interval: u32 },
+    RegisterEquals(i32),
+    RegisterAbove(i32),
+    RegisterBelow(i32),
+}
+
+impl Condition {
+    pub fn matches(&self, tick: &Tick) -> bool {
+        match *self {
+            Condition::Cycle(cycle) => tick.cycle == cycle,
+            Condition::EveryCycles { start, interval } => {
+                tick.cycle >= start && (tick.cycle - start).is_multiple_of(interval)
+            }
+            Condition::RegisterEquals(value) => tick.x == value,
+            Condition::RegisterAbove(value) => tick.x > value,
+            Condition::RegisterBelow(value) => tick.x < value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    // Stops the run after the cycle, it can be resumed with another call to run
+    Break,
+    // Only reports the cycle
+    Watch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Breakpoint {
+    pub condition: Condition,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Halt {
+    Finished,
+    Breakpoint(Tick),
+}
+
+pub struct Cpu {
+    program: Vec<Instruction>,
+    // Index of the current instruction, and the cycles already spent on it
+    pc: usize,
+    elapsed: u32,
+    pub cycle: u32,
+    pub x: i32,
+    // Prints every cycle as it executes
+    pub trace: bool,
+    breakpoints: Vec<Breakpoint>,
+}
+
+impl Cpu {
+    pub fn new(program: Vec<Instruction>) -> Self {
+        Cpu {
+            program,
+            pc: 0,
+            elapsed: 0,
+            cycle: 0,
+            x: 1,
+            trace: false,
+            breakpoints: Vec::new(),
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, condition: Condition) {
+        self.breakpoints.push(Breakpoint {
+            condition,
+            action: Action::Break,
+        });
+    }
+
+    pub fn add_watch(&mut self, condition: Condition) {
+        self.breakpoints.push(Breakpoint {
+            condition,
+            action: Action::Watch,
+        });
+    }
+
+    pub fn finished(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    // Executes a single cycle, None once the program has finished
+    pub fn step(&mut self) -> Option<Tick> {
+        let instruction = *self.program.get(self.pc)?;
+        self.cycle += 1;
+        self.elapsed += 1;
+
+        let tick = Tick {
+            cycle: self.cycle,
+            x: self.x,
+            instruction,
+        };
+
+        // The effect only lands after the last cycle of the instruction
+        if self.elapsed == instruction.spec.cycles {
+            self.x = (instruction.spec.effect)(self.x, instruction.operand);
+            self.pc += 1;
+            self.elapsed = 0;
+        }
+
+        Some(tick)
+    }
+
+    // Runs until the program finishes or a breakpoint is hit, calling the observer during every cycle
+    pub fn run(&mut self, mut observer: impl FnMut(&Tick)) -> Halt {
+        while let Some(tick) = self.step() {
+            if self.trace {
+                println!(
+                    "Cycle {:>4} | X = {:>3} | {}",
+                    tick.cycle, tick.x, tick.instruction
+                );
+            }
+
+            observer(&tick);
+
+            let mut halt = false;
+            for breakpoint in self.breakpoints.iter() {
+                if !breakpoint.condition.matches(&tick) {
+                    continue;
+                }
+
+                match breakpoint.action {
+                    Action::Watch => println!(
+                        "Watch {:?} - cycle {}, X = {}, executing {}",
+                        breakpoint.condition, tick.cycle, tick.x, tick.instruction
+                    ),
+                    Action::Break => halt = true,
+                }
+            }
+
+            if halt {
+                return Halt::Breakpoint(tick);
+            }
+        }
+
+        Halt::Finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The larger example program from the puzzle statement
+    const EXAMPLE: &str = include_str!("../inputs/input10_modified.txt");
+
+    #[test]
+    fn small_program() {
+        let program = parse_program("noop\naddx 3\naddx -5\n".as_bytes()).unwrap();
+        let mut cpu = Cpu::new(program);
+        let mut during = Vec::new();
+        let halt = cpu.run(|tick| during.push((tick.cycle, tick.x)));
+
+        assert!(matches!(halt, Halt::Finished));
+        assert_eq!(during, [(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+        assert_eq!(cpu.x, -1);
+        assert!(cpu.finished());
+    }
+
+    #[test]
+    fn example_signal_strength() {
+        let mut cpu = Cpu::new(parse_program(EXAMPLE.as_bytes()).unwrap());
+        let samples = Condition::EveryCycles {
+            start: 20,
+            interval: 40,
+        };
+        let mut signal = 0;
+        cpu.run(|tick| {
+            if samples.matches(tick) {
+                signal += tick.cycle as i32 * tick.x;
+            }
+        });
+        assert_eq!(signal, 13140);
+    }
+
+    #[test]
+    fn every_cycles() {
+        let condition = Condition::EveryCycles {
+            start: 20,
+            interval: 40,
+        };
+        let noop = Instruction::new("noop", None).unwrap();
+        let matching = (1..=240)
+            .filter(|cycle| {
+                condition.matches(&Tick {
+                    cycle: *cycle,
+                    x: 1,
+                    instruction: noop,
+                })
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(matching, [20, 60, 100, 140, 180, 220]);
+    }
+
+    #[test]
+    fn break_after_the_observer() {
+        let mut cpu = Cpu::new(parse_program(EXAMPLE.as_bytes()).unwrap());
+        cpu.add_breakpoint(Condition::Cycle(220));
+
+        let mut last_seen = 0;
+        let halt = cpu.run(|tick| last_seen = tick.cycle);
+        match halt {
+            Halt::Breakpoint(tick) => assert_eq!((tick.cycle, tick.x), (220, 18)),
+            Halt::Finished => panic!("The breakpoint was never hit"),
+        }
+        assert_eq!(last_seen, 220);
+
+        // Resuming carries on from the next cycle
+        let halt = cpu.run(|tick| last_seen = tick.cycle);
+        assert!(matches!(halt, Halt::Finished));
+        assert_eq!(last_seen, 240);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = parse_program("noop\naddx\n".as_bytes()).unwrap_err();
+        assert_eq!(error.line, 2);
+        let error = parse_program("jump 3\n".as_bytes()).unwrap_err();
+        assert_eq!(error.line, 1);
+    }
+}
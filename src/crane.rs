@@ -0,0 +1,300 @@
+// Crane engine for puzzle 5 - Rearranging stacks of crates, one move at a time
+use std::io::BufRead;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Model {
+    // Moves one crate at a time, so a move reverses the order of the crates
+    CrateMover9000,
+    // Moves all the crates at once, keeping their order
+    CrateMover9001,
+}
+
+// Stacks are numbered from 1, as in the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl std::fmt::Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "move {} from {} to {}", self.count, self.from, self.to)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveError {
+    UnknownStack(usize),
+    NotEnoughCrates {
+        stack: usize,
+        requested: usize,
+        available: usize,
+    },
+}
+
+impl std::fmt::Display for MoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveError::UnknownStack(stack) => write!(f, "there is no stack {}", stack),
+            MoveError::NotEnoughCrates {
+                stack,
+                requested,
+                available,
+            } => write!(
+                f,
+                "stack {} only has {} crates, {} requested",
+                stack, available, requested
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // 1-based line of the input where parsing failed
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Parses the drawing of the stacks, then the rearrangement procedure after the blank line
+pub fn parse_input(reader: impl BufRead) -> Result<(Vec<Vec<char>>, Vec<Move>), ParseError> {
+    let lines = reader.lines().map(|line| line.unwrap()).collect::<Vec<_>>();
+    let blank = lines
+        .iter()
+        .position(|line| line.trim().is_empty())
+        .ok_or_else(|| ParseError {
+            line: lines.len(),
+            message: "missing the blank line after the drawing".to_owned(),
+        })?;
+
+    Ok((
+        parse_drawing(&lines[..blank])?,
+        parse_procedure(&lines[blank + 1..], blank + 2)?,
+    ))
+}
+
+// The last line of the drawing numbers the stacks - crates sit in the columns of the numbers
+fn parse_drawing(lines: &[String]) -> Result<Vec<Vec<char>>, ParseError> {
+    let (numbers, crates) = lines.split_last().ok_or(ParseError {
+        line: 1,
+        message: "missing the drawing of the stacks".to_owned(),
+    })?;
+    let columns = numbers
+        .char_indices()
+        .filter(|(_, c)| c.is_ascii_digit())
+        .map(|(column, _)| column)
+        .collect::<Vec<_>>();
+
+    let mut stacks = vec![Vec::new(); columns.len()];
+    // Filling the stacks from the bottom of the drawing
+    for (index, line) in crates.iter().enumerate().rev() {
+        let chars = line.chars().collect::<Vec<_>>();
+        for (stack, column) in columns.iter().enumerate() {
+            match chars.get(*column) {
+                Some(c) if c.is_ascii_alphabetic() => stacks[stack].push(*c),
+                Some(' ') | None => {}
+                Some(c) => {
+                    return Err(ParseError {
+                        line: index + 1,
+                        message: format!("unexpected '{}' in stack {}", c, stack + 1),
+                    })
+                }
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+fn parse_procedure(lines: &[String], first_line: usize) -> Result<Vec<Move>, ParseError> {
+    let pattern = regex::Regex::new("^move ([0-9]+) from ([0-9]+) to ([0-9]+)$").unwrap();
+    let mut moves = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let captures = pattern.captures(line.trim()).ok_or_else(|| ParseError {
+            line: first_line + index,
+            message: format!("malformed move \"{}\"", line),
+        })?;
+        let number = |group: usize| {
+            let digits = captures.get(group).unwrap().as_str();
+            digits.parse::<usize>().map_err(|err| ParseError {
+                line: first_line + index,
+                message: format!("bad number {} in \"{}\": {}", digits, line, err),
+            })
+        };
+        moves.push(Move {
+            count: number(1)?,
+            from: number(2)?,
+            to: number(3)?,
+        });
+    }
+
+    Ok(moves)
+}
+
+pub struct Crane {
+    pub model: Model,
+    // Bottom crate first
+    stacks: Vec<Vec<char>>,
+}
+
+impl Crane {
+    pub fn new(stacks: Vec<Vec<char>>, model: Model) -> Self {
+        Crane { model, stacks }
+    }
+
+    pub fn stacks(&self) -> &[Vec<char>] {
+        &self.stacks
+    }
+
+    // Checks the move before touching the stacks, so a failed move leaves them as they were
+    pub fn apply(&mut self, step: &Move) -> Result<(), MoveError> {
+        for stack in [step.from, step.to] {
+            if stack == 0 || stack > self.stacks.len() {
+                return Err(MoveError::UnknownStack(stack));
+            }
+        }
+        let available = self.stacks[step.from - 1].len();
+        if available < step.count {
+            return Err(MoveError::NotEnoughCrates {
+                stack: step.from,
+                requested: step.count,
+                available,
+            });
+        }
+
+        // Putting the crates back where they came from leaves the stack as it was, with either model
+        if step.from == step.to {
+            return Ok(());
+        }
+
+        let mut grabbed = self.stacks[step.from - 1].split_off(available - step.count);
+        if self.model == Model::CrateMover9000 {
+            grabbed.reverse();
+        }
+        self.stacks[step.to - 1].append(&mut grabbed);
+        Ok(())
+    }
+
+    // The crate on top of each stack - empty stacks are skipped
+    pub fn top_crates(&self) -> String {
+        self.stacks
+            .iter()
+            .filter_map(|stack| stack.last())
+            .collect()
+    }
+
+    // The stacks drawn as in the puzzle - a row of [A] [B] crates per level, then the stack numbers
+    pub fn render(&self) -> String {
+        let height = self
+            .stacks
+            .iter()
+            .map(|stack| stack.len())
+            .max()
+            .unwrap_or(0);
+        let mut output = String::new();
+
+        for level in (0..height).rev() {
+            let row = self
+                .stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(c) => format!("[{}]", c),
+                    None => "   ".to_owned(),
+                })
+                .collect::<Vec<_>>();
+            output.push_str(&row.join(" "));
+            output.push('\n');
+        }
+
+        let numbers = (1..=self.stacks.len())
+            .map(|number| format!("{:^3}", number))
+            .collect::<Vec<_>>();
+        output.push_str(&numbers.join(" "));
+        output.push('\n');
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+    fn run(model: Model) -> Crane {
+        let (stacks, moves) = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let mut crane = Crane::new(stacks, model);
+        for step in moves.iter() {
+            crane.apply(step).unwrap();
+        }
+        crane
+    }
+
+    #[test]
+    fn example_top_crates() {
+        assert_eq!(run(Model::CrateMover9000).top_crates(), "CMZ");
+        assert_eq!(run(Model::CrateMover9001).top_crates(), "MCD");
+    }
+
+    #[test]
+    fn moving_onto_the_same_stack() {
+        for model in [Model::CrateMover9000, Model::CrateMover9001] {
+            let mut crane = Crane::new(vec![vec!['A', 'B', 'C']], model);
+            let step = Move {
+                count: 3,
+                from: 1,
+                to: 1,
+            };
+            assert_eq!(crane.apply(&step), Ok(()));
+            assert_eq!(crane.stacks(), &[vec!['A', 'B', 'C']]);
+        }
+    }
+
+    #[test]
+    fn invalid_moves() {
+        let mut crane = Crane::new(vec![vec!['A'], vec![]], Model::CrateMover9000);
+        let step = |count, from, to| Move { count, from, to };
+        assert_eq!(crane.apply(&step(1, 3, 1)), Err(MoveError::UnknownStack(3)));
+        assert_eq!(crane.apply(&step(1, 1, 0)), Err(MoveError::UnknownStack(0)));
+        assert_eq!(
+            crane.apply(&step(2, 1, 2)),
+            Err(MoveError::NotEnoughCrates {
+                stack: 1,
+                requested: 2,
+                available: 1
+            })
+        );
+        assert_eq!(crane.stacks(), &[vec!['A'], vec![]]);
+    }
+
+    #[test]
+    fn oversized_numbers_are_parse_errors() {
+        let input = "[A]\n 1 \n\nmove 1 from 1 to 1\nmove 99999999999999999999999 from 1 to 1\n";
+        let error = parse_input(input.as_bytes()).unwrap_err();
+        assert_eq!(error.line, 5);
+    }
+}
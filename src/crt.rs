@@ -0,0 +1,279 @@
+// CRT display for puzzle 10 - 40x6 pixels, drawing capital letters in the AoC font
+use crate::cpu::Instruction;
+
+pub const WIDTH: usize = 40;
+pub const HEIGHT: usize = 6;
+// Each letter is 4 pixels wide, followed by a column of spacing
+pub const GLYPH_WIDTH: usize = 4;
+pub const CELL_WIDTH: usize = 5;
+
+pub const FONT: &[(char, [&str; HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownGlyph {
+    // 0-based index of the letter on the display
+    pub index: usize,
+    pub bitmap: [String; HEIGHT],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownGlyphs(pub Vec<UnknownGlyph>);
+
+impl std::fmt::Display for UnknownGlyphs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} unknown glyph(s) on the display:", self.0.len())?;
+        for glyph in self.0.iter() {
+            writeln!(f, "Letter {}:", glyph.index + 1)?;
+            for row in glyph.bitmap.iter() {
+                writeln!(f, "{}", row)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnknownGlyphs {}
+
+// Bitmap of a single letter in the font, None if the font does not contain it
+pub fn glyph(letter: char) -> Option<&'static [&'static str; HEIGHT]> {
+    FONT.iter()
+        .find(|(c, _)| *c == letter.to_ascii_uppercase())
+        .map(|(_, bitmap)| bitmap)
+}
+
+// Reads the letters off the display, given as rows of pixels with '#' for the lit pixels.
+// Unknown glyphs are all collected into the error, so they can be added to the font.
+pub fn read_letters(pixels: &[char]) -> Result<String, UnknownGlyphs> {
+    let rows = pixels.chunks(WIDTH).collect::<Vec<_>>();
+    let mut letters = String::new();
+    let mut unknown = Vec::new();
+
+    for index in 0..(WIDTH / CELL_WIDTH) {
+        let start = index * CELL_WIDTH;
+        let bitmap: [String; HEIGHT] = std::array::from_fn(|row| {
+            (start..start + GLYPH_WIDTH)
+                .map(|col| match rows.get(row).and_then(|r| r.get(col)) {
+                    Some('#') => '#',
+                    _ => '.',
+                })
+                .collect()
+        });
+
+        match FONT
+            .iter()
+            .find(|(_, glyph)| glyph.iter().eq(bitmap.iter()))
+        {
+            Some((letter, _)) => letters.push(*letter),
+            None => unknown.push(UnknownGlyph { index, bitmap }),
+        }
+    }
+
+    match unknown.is_empty() {
+        true => Ok(letters),
+        false => Err(UnknownGlyphs(unknown)),
+    }
+}
+
+// Whether the CRT lights the pixel drawn during the cycle - the sprite is 3 pixels wide, centred on X
+pub fn pixel_lit(cycle: u32, x: i32) -> bool {
+    let column = (cycle as i32 - 1).rem_euclid(WIDTH as i32);
+    (column - x).abs() <= 1
+}
+
+// The whole display with a word drawn in the font, None if the font lacks one of the letters
+pub fn draw_word(word: &str) -> Option<Vec<char>> {
+    let letters = word.chars().collect::<Vec<_>>();
+    if letters.len() > WIDTH / CELL_WIDTH {
+        return None;
+    }
+
+    let glyphs = letters
+        .iter()
+        .map(|letter| glyph(*letter))
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut pixels = vec!['.'; WIDTH * HEIGHT];
+    for (index, glyph) in glyphs.iter().enumerate() {
+        for (row, line) in glyph.iter().enumerate() {
+            for (col, pixel) in line.chars().enumerate() {
+                pixels[row * WIDTH + index * CELL_WIDTH + col] = pixel;
+            }
+        }
+    }
+
+    Some(pixels)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Undrawable {
+    // 0-based position of the first pixel no program can reach
+    pub row: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for Undrawable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "no program can draw the image up to row {}, column {}",
+            self.row + 1,
+            self.column + 1
+        )
+    }
+}
+
+impl std::error::Error for Undrawable {}
+
+// Builds a program that draws the image, with as few instructions as possible.
+// A frame always takes 240 cycles, so fewer instructions means as many 2-cycle addx as possible.
+// The best program is found with dynamic programming over (cycle, X) - X only needs to range
+// a little past the edges of the display, everything further out draws the same dark pixels.
+// X starts at 1 and no instruction completes before the end of cycle 1, so pixels 0 and 1 are
+// always lit - only words whose first letter has a lit top left pair (E, F, Z) can be drawn.
+pub fn compile_image(pixels: &[char]) -> Result<Vec<Instruction>, Undrawable> {
+    const MIN_X: i32 = -2;
+    const MAX_X: i32 = WIDTH as i32 + 1;
+    let cycles = WIDTH * HEIGHT;
+    let states = (MAX_X - MIN_X + 1) as usize;
+    let matches =
+        |cycle: usize, x: i32| pixel_lit(cycle as u32 + 1, x) == (pixels.get(cycle) == Some(&'#'));
+
+    // Most addx used to draw the first `cycle` pixels and end up with X, and the step that got there
+    let mut best: Vec<Vec<Option<(u32, Instruction, i32)>>> = vec![vec![None; states]; cycles + 1];
+    let noop = Instruction::new("noop", None).unwrap();
+    let start = (1 - MIN_X) as usize;
+    best[0][start] = Some((0, noop, 1));
+    let mut furthest = 0;
+
+    for cycle in 0..cycles {
+        for index in 0..states {
+            let Some((count, _, _)) = best[cycle][index] else {
+                continue;
+            };
+            let x = MIN_X + index as i32;
+            if !matches(cycle, x) {
+                continue;
+            }
+            furthest = furthest.max(cycle + 1);
+
+            let mut relax = |next: usize, target: usize, count: u32, instruction: Instruction| {
+                if best[next][target].is_none_or(|(best, _, _)| count > best) {
+                    best[next][target] = Some((count, instruction, x));
+                }
+            };
+            relax(cycle + 1, index, count, noop);
+
+            if cycle + 1 < cycles && matches(cycle + 1, x) {
+                for target in 0..states {
+                    let operand = target as i32 - index as i32;
+                    let addx = Instruction::new("addx", Some(operand)).unwrap();
+                    relax(cycle + 2, target, count + 1, addx);
+                }
+            }
+        }
+    }
+
+    // Walking back from the best end state
+    let end = (0..states)
+        .filter(|index| best[cycles][*index].is_some())
+        .max_by_key(|index| best[cycles][*index].unwrap().0)
+        .ok_or(Undrawable {
+            row: furthest / WIDTH,
+            column: furthest % WIDTH,
+        })?;
+
+    let mut program = Vec::new();
+    let mut cycle = cycles;
+    let mut index = end;
+    while cycle > 0 {
+        let (_, instruction, x) = best[cycle][index].unwrap();
+        program.push(instruction);
+        cycle -= instruction.spec.cycles as usize;
+        index = (x - MIN_X) as usize;
+    }
+    program.reverse();
+
+    Ok(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Runs the program, collecting the pixels the CRT draws
+    fn display(program: Vec<Instruction>) -> Vec<char> {
+        let mut pixels = Vec::new();
+        crate::cpu::Cpu::new(program).run(|tick| match pixel_lit(tick.cycle, tick.x) {
+            true => pixels.push('#'),
+            false => pixels.push('.'),
+        });
+        pixels
+    }
+
+    #[test]
+    fn compiled_word_draws_the_image() {
+        let target = draw_word("FPGPHFGH").unwrap();
+        let program = compile_image(&target).unwrap();
+        assert_eq!(program.len(), 120);
+        assert_eq!(display(program), target);
+    }
+
+    #[test]
+    fn first_pixels_always_lit() {
+        let target = draw_word("HELLOABZ").unwrap();
+        let error = compile_image(&target).unwrap_err();
+        assert_eq!(error, Undrawable { row: 0, column: 1 });
+        assert_eq!(
+            error.to_string(),
+            "no program can draw the image up to row 1, column 2"
+        );
+    }
+
+    #[test]
+    fn font_round_trip() {
+        let letters = FONT.iter().map(|(letter, _)| *letter).collect::<Vec<_>>();
+        // Every word fills the display, wrapping around the font for the last one
+        for start in (0..letters.len()).step_by(WIDTH / CELL_WIDTH) {
+            let word = letters
+                .iter()
+                .cycle()
+                .skip(start)
+                .take(WIDTH / CELL_WIDTH)
+                .collect::<String>();
+            assert_eq!(read_letters(&draw_word(&word).unwrap()), Ok(word));
+        }
+    }
+
+    #[test]
+    fn corrupted_letter_is_unknown() {
+        let mut pixels = draw_word("EHZGRJAP").unwrap();
+        // Turning off the top right pixel of the H
+        pixels[CELL_WIDTH + GLYPH_WIDTH - 1] = '.';
+
+        let unknown = read_letters(&pixels).unwrap_err().0;
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].index, 1);
+        assert_eq!(
+            unknown[0].bitmap,
+            ["#...", "#..#", "####", "#..#", "#..#", "#..#"].map(String::from)
+        );
+    }
+}
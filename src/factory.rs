@@ -0,0 +1,332 @@
+// Robot factory for puzzle 19 - Blueprints, resources and replaying a build order
+use std::ops::{Add, Sub};
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Robot {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Resources {
+    pub ore: u16,
+    pub clay: u16,
+    pub obsidian: u16,
+    pub geode: u16,
+}
+
+impl Resources {
+    pub fn new(ore: u16, clay: u16, obsidian: u16, geode: u16) -> Self {
+        Resources {
+            ore,
+            clay,
+            obsidian,
+            geode,
+        }
+    }
+}
+
+impl Sub for Resources {
+    type Output = Option<Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        if (self.ore < rhs.ore)
+            | (self.clay < rhs.clay)
+            | (self.obsidian < rhs.obsidian)
+            | (self.geode < rhs.geode)
+        {
+            return None;
+        }
+
+        Some(Resources {
+            ore: self.ore - rhs.ore,
+            clay: self.clay - rhs.clay,
+            obsidian: self.obsidian - rhs.obsidian,
+            geode: self.geode - rhs.geode,
+        })
+    }
+}
+
+impl Add for Resources {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Resources {
+            ore: self.ore + rhs.ore,
+            clay: self.clay + rhs.clay,
+            obsidian: self.obsidian + rhs.obsidian,
+            geode: self.geode + rhs.geode,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Robots {
+    pub ore: u16,
+    pub clay: u16,
+    pub obsidian: u16,
+    pub geode: u16,
+}
+
+impl Robots {
+    pub fn new(ore: u16, clay: u16, obsidian: u16, geode: u16) -> Self {
+        Robots {
+            ore,
+            clay,
+            obsidian,
+            geode,
+        }
+    }
+
+    pub fn mine_resources(&self, resources: &mut Resources) {
+        let amount_of_ore_mined = self.ore;
+        resources.ore += amount_of_ore_mined;
+        let amount_of_clay_mined = self.clay;
+        resources.clay += amount_of_clay_mined;
+        let amount_of_obsidian_mined = self.obsidian;
+        resources.obsidian += amount_of_obsidian_mined;
+        let amount_of_geode_mined = self.geode;
+        resources.geode += amount_of_geode_mined;
+    }
+}
+
+impl Sub for Robots {
+    type Output = Option<Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        if (self.ore < rhs.ore)
+            | (self.clay < rhs.clay)
+            | (self.obsidian < rhs.obsidian)
+            | (self.geode < rhs.geode)
+        {
+            return None;
+        }
+
+        Some(Robots {
+            ore: self.ore - rhs.ore,
+            clay: self.clay - rhs.clay,
+            obsidian: self.obsidian - rhs.obsidian,
+            geode: self.geode - rhs.geode,
+        })
+    }
+}
+
+impl Add for Robots {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Robots {
+            ore: self.ore + rhs.ore,
+            clay: self.clay + rhs.clay,
+            obsidian: self.obsidian + rhs.obsidian,
+            geode: self.geode + rhs.geode,
+        }
+    }
+}
+
+impl Robot {
+    fn description(&self) -> &str {
+        match self {
+            Robot::Ore => "ore-collecting",
+            Robot::Clay => "clay-collecting",
+            Robot::Obsidian => "obsidian-collecting",
+            Robot::Geode => "geode-cracking",
+        }
+    }
+
+    fn article(&self) -> &str {
+        match self {
+            Robot::Ore | Robot::Obsidian => "an",
+            Robot::Clay | Robot::Geode => "a",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Blueprint {
+    pub ore_bot: Resources,
+    pub clay_bot: Resources,
+    pub obs_bot: Resources,
+    pub geo_bot: Resources,
+}
+
+impl Blueprint {
+    pub fn cost(&self, robot: Robot) -> Resources {
+        match robot {
+            Robot::Ore => self.ore_bot,
+            Robot::Clay => self.clay_bot,
+            Robot::Obsidian => self.obs_bot,
+            Robot::Geode => self.geo_bot,
+        }
+    }
+}
+
+// Each entry in a plan is the decision taken at the end of a minute
+// i.e. plan[0] is the robot queued after the 1st minute of mining, which is built during the 2nd minute
+pub type Plan = Vec<Option<Robot>>;
+
+fn describe_cost(cost: &Resources) -> String {
+    let mut parts = Vec::new();
+    if cost.ore > 0 {
+        parts.push(format!("{} ore", cost.ore));
+    }
+    if cost.clay > 0 {
+        parts.push(format!("{} clay", cost.clay));
+    }
+    if cost.obsidian > 0 {
+        parts.push(format!("{} obsidian", cost.obsidian));
+    }
+    parts.join(" and ")
+}
+
+fn describe_collection(robot: Robot, robot_count: u16, total: u16) -> String {
+    let plural = |count: u16| if count == 1 { "" } else { "s" };
+    match robot {
+        Robot::Geode => format!(
+            "{} geode-cracking robot{} crack{} {} geode{}; you now have {} open geode{}.",
+            robot_count,
+            plural(robot_count),
+            if robot_count == 1 { "s" } else { "" },
+            robot_count,
+            plural(robot_count),
+            total,
+            plural(total)
+        ),
+        _ => {
+            let resource = match robot {
+                Robot::Ore => "ore",
+                Robot::Clay => "clay",
+                _ => "obsidian",
+            };
+            format!(
+                "{} {} robot{} collect{} {} {}; you now have {} {}.",
+                robot_count,
+                robot.description(),
+                plural(robot_count),
+                if robot_count == 1 { "s" } else { "" },
+                robot_count,
+                resource,
+                total,
+                resource
+            )
+        }
+    }
+}
+
+// Replaying the plan found by the search, printing each minute in the same format as the puzzle text
+// Returns the number of geodes opened, which should match the result of the search
+pub fn print_schedule(blueprint: &Blueprint, plan: &Plan, total_time: u16) -> u16 {
+    let mut robots = Robots::new(1, 0, 0, 0);
+    let mut resources = Resources::new(0, 0, 0, 0);
+
+    for minute in 1..=total_time {
+        println!("== Minute {} ==", minute);
+
+        // The robot queued at the end of the previous minute is built during this minute
+        let building = match minute {
+            1 => None,
+            _ => plan.get(minute as usize - 2).copied().flatten(),
+        };
+        if let Some(robot) = building {
+            let cost = blueprint.cost(robot);
+            resources = (resources - cost).expect("Plan spends more resources than available.");
+            println!(
+                "Spend {} to start building {} {} robot.",
+                describe_cost(&cost),
+                robot.article(),
+                robot.description()
+            );
+        }
+
+        robots.mine_resources(&mut resources);
+        let collected = [
+            (Robot::Ore, robots.ore, resources.ore),
+            (Robot::Clay, robots.clay, resources.clay),
+            (Robot::Obsidian, robots.obsidian, resources.obsidian),
+            (Robot::Geode, robots.geode, resources.geode),
+        ];
+        for (robot, robot_count, total) in collected {
+            if robot_count > 0 {
+                println!("{}", describe_collection(robot, robot_count, total));
+            }
+        }
+
+        if let Some(robot) = building {
+            let robot_count = match robot {
+                Robot::Ore => {
+                    robots.ore += 1;
+                    robots.ore
+                }
+                Robot::Clay => {
+                    robots.clay += 1;
+                    robots.clay
+                }
+                Robot::Obsidian => {
+                    robots.obsidian += 1;
+                    robots.obsidian
+                }
+                Robot::Geode => {
+                    robots.geode += 1;
+                    robots.geode
+                }
+            };
+            println!(
+                "The new {} robot is ready; you now have {} of them.",
+                robot.description(),
+                robot_count
+            );
+        }
+        println!();
+    }
+
+    resources.geode
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_schedule() {
+        // Blueprint 1 from the puzzle statement
+        let blueprint = Blueprint {
+            ore_bot: Resources::new(4, 0, 0, 0),
+            clay_bot: Resources::new(2, 0, 0, 0),
+            obs_bot: Resources::new(3, 14, 0, 0),
+            geo_bot: Resources::new(2, 0, 7, 0),
+        };
+        // The robots built during minutes 3, 5, 7, 11, 12, 15, 18 and 21 of the puzzle text
+        let mut plan: Plan = vec![None; 23];
+        for (minute, robot) in [
+            (3, Robot::Clay),
+            (5, Robot::Clay),
+            (7, Robot::Clay),
+            (11, Robot::Obsidian),
+            (12, Robot::Clay),
+            (15, Robot::Obsidian),
+            (18, Robot::Geode),
+            (21, Robot::Geode),
+        ] {
+            plan[minute - 2] = Some(robot);
+        }
+        assert_eq!(print_schedule(&blueprint, &plan, 24), 9);
+    }
+
+    #[test]
+    fn descriptions() {
+        assert_eq!(
+            describe_cost(&Resources::new(3, 14, 0, 0)),
+            "3 ore and 14 clay"
+        );
+        assert_eq!(
+            describe_collection(Robot::Geode, 1, 1),
+            "1 geode-cracking robot cracks 1 geode; you now have 1 open geode."
+        );
+        assert_eq!(
+            describe_collection(Robot::Clay, 3, 9),
+            "3 clay-collecting robots collect 3 clay; you now have 9 clay."
+        );
+    }
+}
@@ -0,0 +1,453 @@
+// Tree survey for puzzle 8 - Visibility and viewing distances for a grid of tree heights
+use std::io::{BufRead, Write};
+
+// Characters for the ASCII score map, from the lowest scores to the highest
+const SCORE_SHADES: &[u8] = b" .:-=+*#%@";
+const EDGE_COLOUR: [u8; 3] = [40, 20, 20];
+
+// Viewing directions, in the order the distances are stored
+pub const DIRECTIONS: [&str; 4] = ["up", "left", "down", "right"];
+const UP: usize = 0;
+const LEFT: usize = 1;
+const DOWN: usize = 2;
+const RIGHT: usize = 3;
+
+pub fn parse_forest(reader: impl BufRead) -> Vec<Vec<u32>> {
+    let mut matrix = Vec::new();
+
+    // Filling up the matrix
+    for line in reader.lines() {
+        let line = line.unwrap();
+        let row = line.chars().map(|c| c.to_digit(10).unwrap()).collect();
+        matrix.push(row)
+    }
+
+    matrix
+}
+
+// A tree and the view from it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spot {
+    pub pos: (usize, usize),
+    pub height: u32,
+    pub score: u32,
+    pub distances: [u32; 4],
+}
+
+impl std::fmt::Display for Spot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "row {}, col {} (height {}) - score {}",
+            self.pos.0, self.pos.1, self.height, self.score
+        )?;
+        for (direction, distance) in DIRECTIONS.iter().zip(self.distances.iter()) {
+            write!(f, ", {} {}", direction, distance)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Survey {
+    // Whether the tree can be seen from outside the grid, from any of the directions
+    pub visible: Vec<Vec<bool>>,
+    // Number of trees seen from the tree in each of the directions
+    pub distances: Vec<Vec<[u32; 4]>>,
+}
+
+impl Survey {
+    // Sweeps every row and column once from each end, so the whole survey is linear in the grid size.
+    // A monotonic stack holds the trees seen so far that are not hidden behind a taller or equal
+    // tree - popping the shorter ones leaves the tree blocking the view on top.
+    pub fn new(matrix: &[Vec<u32>]) -> Self {
+        let rows = matrix.len();
+        let cols = matrix.first().map_or(0, |row| row.len());
+        let mut survey = Survey {
+            visible: vec![vec![false; cols]; rows],
+            distances: vec![vec![[0; 4]; cols]; rows],
+        };
+
+        for i in 0..rows {
+            survey.sweep(matrix, LEFT, (0..cols).map(|j| (i, j)));
+            survey.sweep(matrix, RIGHT, (0..cols).rev().map(|j| (i, j)));
+        }
+        for j in 0..cols {
+            survey.sweep(matrix, UP, (0..rows).map(|i| (i, j)));
+            survey.sweep(matrix, DOWN, (0..rows).rev().map(|i| (i, j)));
+        }
+
+        survey
+    }
+
+    // Walks a line of trees starting from the edge the trees are looking towards
+    fn sweep(
+        &mut self,
+        matrix: &[Vec<u32>],
+        direction: usize,
+        line: impl Iterator<Item = (usize, usize)>,
+    ) {
+        // Index along the line and height of the trees that can still block a view
+        let mut stack: Vec<(u32, u32)> = Vec::new();
+
+        for (index, (i, j)) in line.enumerate() {
+            let index = index as u32;
+            let height = matrix[i][j];
+            while matches!(stack.last(), Some((_, other)) if *other < height) {
+                stack.pop();
+            }
+
+            match stack.last() {
+                // Blocked - the view ends at the blocking tree
+                Some((other, _)) => self.distances[i][j][direction] = index - other,
+                // Nothing as tall in the way - sees all the way to the edge
+                None => {
+                    self.distances[i][j][direction] = index;
+                    self.visible[i][j] = true;
+                }
+            }
+
+            stack.push((index, height));
+        }
+    }
+
+    pub fn visible_count(&self) -> usize {
+        self.visible
+            .iter()
+            .flatten()
+            .filter(|visible| **visible)
+            .count()
+    }
+
+    pub fn scenic_score(&self, pos: (usize, usize)) -> u32 {
+        self.distances[pos.0][pos.1].iter().product()
+    }
+
+    pub fn max_scenic_score(&self) -> u32 {
+        self.positions()
+            .map(|pos| self.scenic_score(pos))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.distances.len()).flat_map(|i| (0..self.distances[i].len()).map(move |j| (i, j)))
+    }
+
+    // The k best scoring trees, highest first - ties go to the tree nearest the top left
+    pub fn top_spots(&self, matrix: &[Vec<u32>], k: usize) -> Vec<Spot> {
+        let mut spots = self
+            .positions()
+            .map(|pos| Spot {
+                pos,
+                height: matrix[pos.0][pos.1],
+                score: self.scenic_score(pos),
+                distances: self.distances[pos.0][pos.1],
+            })
+            .collect::<Vec<_>>();
+        spots.sort_by(|a, b| b.score.cmp(&a.score).then(a.pos.cmp(&b.pos)));
+        spots.truncate(k);
+        spots
+    }
+
+    // Heights of the visible trees, with the hidden ones as '.'
+    pub fn render_visibility(&self, matrix: &[Vec<u32>]) -> String {
+        let mut output = String::new();
+        for (i, row) in matrix.iter().enumerate() {
+            for (j, height) in row.iter().enumerate() {
+                match self.visible[i][j] {
+                    true => output.push(char::from_digit(*height, 10).unwrap()),
+                    false => output.push('.'),
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    // Scores span several orders of magnitude, so they are shaded on a log scale from 0 to 1
+    fn shade(&self, pos: (usize, usize), max: u32) -> f64 {
+        (1.0 + self.scenic_score(pos) as f64).ln() / (1.0 + max.max(1) as f64).ln()
+    }
+
+    pub fn render_scores(&self) -> String {
+        let max = self.max_scenic_score();
+        let mut output = String::new();
+        for i in 0..self.distances.len() {
+            for j in 0..self.distances[i].len() {
+                let level = self.shade((i, j), max) * (SCORE_SHADES.len() - 1) as f64;
+                output.push(SCORE_SHADES[level.round() as usize] as char);
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    // Writes the scores as a netpbm image - black to yellow, with the trees scoring 0 in dark red
+    pub fn write_score_image(&self, path: &str) -> std::io::Result<()> {
+        if let Some(parent) = std::path::Path::new(path).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let rows = self.distances.len();
+        let cols = self.distances.first().map_or(0, |row| row.len());
+        let max = self.max_scenic_score();
+
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        write!(writer, "P6\n{} {}\n255\n", cols, rows)?;
+        for pos in self.positions() {
+            let pixel = match self.scenic_score(pos) {
+                0 => EDGE_COLOUR,
+                _ => {
+                    let level = (self.shade(pos, max) * 255.0) as u8;
+                    [level, level, level / 3]
+                }
+            };
+            writer.write_all(&pixel)?;
+        }
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "30373\n25512\n65332\n33549\n35390\n";
+
+    // The original per-tree walks in all four directions, kept as the reference for the sweeps
+    fn is_visible(
+        arr: &[Vec<u32>],
+        val: u32,
+        pos: (usize, usize),
+        max_pos: (usize, usize),
+    ) -> bool {
+        let mut flag_visible: bool = false;
+
+        // Getting the current value
+        let (mut x, mut y) = pos;
+        let (x_max, y_max) = max_pos;
+        let tree = val;
+
+        // Edges
+        if (x == 0) | (x == x_max) | (y == 0) | (y == y_max) {
+            flag_visible = true;
+            return flag_visible;
+        }
+
+        // Traversing each direction
+
+        // Traversing top direction
+        (x, y) = pos;
+        while x > 0 {
+            x -= 1;
+            let other = arr.get(x).unwrap().get(y).unwrap();
+            if other >= &tree {
+                break;
+            }
+            if x == 0 {
+                flag_visible = true;
+                return flag_visible;
+            }
+        }
+
+        // Traversing bottom direction
+        (x, y) = pos;
+        while x < x_max {
+            x += 1;
+            let other = arr.get(x).unwrap().get(y).unwrap();
+            if other >= &tree {
+                break;
+            }
+            if x == x_max {
+                flag_visible = true;
+                return flag_visible;
+            }
+        }
+
+        // Traversing left direction
+        (x, y) = pos;
+        while y > 0 {
+            y -= 1;
+            let other = arr.get(x).unwrap().get(y).unwrap();
+            if other >= &tree {
+                break;
+            }
+            if y == 0 {
+                flag_visible = true;
+                return flag_visible;
+            }
+        }
+
+        // Traversing right direction
+        (x, y) = pos;
+        while y < y_max {
+            y += 1;
+            let other = arr.get(x).unwrap().get(y).unwrap();
+            if other >= &tree {
+                break;
+            }
+            if y == y_max {
+                flag_visible = true;
+                return flag_visible;
+            }
+        }
+
+        flag_visible
+    }
+
+    fn scenic_score(
+        arr: &[Vec<u32>],
+        val: u32,
+        pos: (usize, usize),
+        max_pos: (usize, usize),
+    ) -> u32 {
+        // Getting the current value
+        let (mut x, mut y) = pos;
+        let (x_max, y_max) = max_pos;
+        let tree = val;
+        let mut score = 1;
+
+        // Edges
+        if (x == 0) | (x == x_max) | (y == 0) | (y == y_max) {
+            return 0;
+        }
+
+        // Traversing each direction
+        let mut current;
+
+        // Traversing top direction
+        (x, y) = pos;
+        current = 0;
+        while x > 0 {
+            x -= 1; // Moves to the new tree
+            current += 1; // Adds an additional tree to the counter
+            let other = arr.get(x).unwrap().get(y).unwrap(); // Evaluating the height of the other tree
+
+            // Reaches a other tree that blocks the current tree - Break loop and update current score.
+            if other >= &tree {
+                score *= current;
+                break;
+            }
+
+            // Reaches the edge - Update current score.
+            if x == 0 {
+                score *= current;
+            }
+        }
+
+        // Traversing bottom direction
+        (x, y) = pos;
+        current = 0;
+        while x < x_max {
+            x += 1; // Moves to the new tree
+            current += 1; // Adds an additional tree to the counter
+            let other = arr.get(x).unwrap().get(y).unwrap(); // Evaluating the height of the other tree
+
+            // Reaches a other tree that blocks the current tree - Break loop and update current score.
+            if other >= &tree {
+                score *= current;
+                break;
+            }
+
+            // Reaches the edge - Update current score.
+            if x == x_max {
+                score *= current;
+            }
+        }
+
+        // Traversing left direction
+        (x, y) = pos;
+        current = 0;
+        while y > 0 {
+            y -= 1; // Moves to the new tree
+            current += 1; // Adds an additional tree to the counter
+            let other = arr.get(x).unwrap().get(y).unwrap(); // Evaluating the height of the other tree
+
+            // Reaches a other tree that blocks the current tree - Break loop and update current score.
+            if other >= &tree {
+                score *= current;
+                break;
+            }
+
+            // Reaches the edge - Update current score.
+            if y == 0 {
+                score *= current;
+            }
+        }
+
+        // Traversing right direction
+        (x, y) = pos;
+        current = 0;
+        while y < y_max {
+            y += 1; // Moves to the new tree
+            current += 1; // Adds an additional tree to the counter
+            let other = arr.get(x).unwrap().get(y).unwrap(); // Evaluating the height of the other tree
+
+            // Reaches a other tree that blocks the current tree - Break loop and update current score.
+            if other >= &tree {
+                score *= current;
+                break;
+            }
+
+            // Reaches the edge - Update current score.
+            if y == y_max {
+                score *= current;
+            }
+        }
+
+        score
+    }
+
+    fn check_against_walk(matrix: &[Vec<u32>]) {
+        let survey = Survey::new(matrix);
+        let i_max = matrix.len() - 1;
+        let j_max = matrix[0].len() - 1;
+        for (i, row) in matrix.iter().enumerate() {
+            for (j, val) in row.iter().enumerate() {
+                let visible = is_visible(matrix, *val, (i, j), (i_max, j_max));
+                let score = scenic_score(matrix, *val, (i, j), (i_max, j_max));
+                assert_eq!(
+                    survey.visible[i][j],
+                    visible,
+                    "Visibility of tree {:?}",
+                    (i, j)
+                );
+                assert_eq!(
+                    survey.scenic_score((i, j)),
+                    score,
+                    "Score of tree {:?}",
+                    (i, j)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn example_matches_walk() {
+        let matrix = parse_forest(EXAMPLE.as_bytes());
+        check_against_walk(&matrix);
+
+        let survey = Survey::new(&matrix);
+        assert_eq!(survey.visible_count(), 21);
+        assert_eq!(survey.max_scenic_score(), 8);
+        assert_eq!(survey.distances[3][2], [2, 2, 1, 2]);
+    }
+
+    #[test]
+    fn generated_grids_match_walk() {
+        // Small heights make equal trees blocking each other common
+        let mut seed: u32 = 12345;
+        for (rows, cols) in [(1, 1), (1, 7), (6, 1), (9, 13), (25, 25)] {
+            let matrix = (0..rows)
+                .map(|_| {
+                    (0..cols)
+                        .map(|_| {
+                            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                            (seed >> 16) % 4
+                        })
+                        .collect()
+                })
+                .collect::<Vec<Vec<u32>>>();
+            check_against_walk(&matrix);
+        }
+    }
+}
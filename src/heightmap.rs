@@ -0,0 +1,287 @@
+// Heightmap for puzzle 12 - Climbing from the lowest point to the best signal
+use std::io::{BufRead, Write};
+
+use crate::search::{self, SearchResult};
+
+const UNREACHABLE_COLOUR: [u8; 3] = [60, 0, 0];
+const ROUTE_COLOUR: [u8; 3] = [255, 60, 60];
+
+// x is the row index, y is the column index
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    // 1-based line and column of the bad character
+    pub line: usize,
+    pub column: usize,
+    pub character: char,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: '{}' is not an elevation",
+            self.line, self.column, self.character
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub struct Heightmap {
+    pub map: Vec<Vec<char>>,
+    elevations: Vec<Vec<u8>>,
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Heightmap {
+    // Elevations are 'a' to 'z', with 'S' marking the start and 'E' the end
+    pub fn parse(reader: impl BufRead) -> Result<Self, ParseError> {
+        let mut map = Vec::new();
+        let mut elevations = Vec::new();
+        let mut start = Position { x: 0, y: 0 };
+        let mut end = Position { x: 0, y: 0 };
+
+        for (i, rline) in reader.lines().enumerate() {
+            let line = rline.unwrap();
+            let row = line.chars().collect::<Vec<_>>();
+            let mut elevation_row = Vec::new();
+            for (j, c) in row.iter().enumerate() {
+                // The start and end carry the lowest and highest elevations respectively
+                let elevation = match c {
+                    'S' => {
+                        start = Position { x: i, y: j };
+                        b'a'
+                    }
+                    'E' => {
+                        end = Position { x: i, y: j };
+                        b'z'
+                    }
+                    c if c.is_ascii_lowercase() => *c as u8,
+                    c => {
+                        return Err(ParseError {
+                            line: i + 1,
+                            column: j + 1,
+                            character: *c,
+                        })
+                    }
+                };
+                elevation_row.push(elevation - b'a');
+            }
+            map.push(row);
+            elevations.push(elevation_row);
+        }
+
+        Ok(Heightmap {
+            map,
+            elevations,
+            start,
+            end,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.elevations.len()
+    }
+
+    pub fn columns(&self) -> usize {
+        self.elevations[0].len()
+    }
+
+    // Elevation relative to 'a'
+    pub fn elevation(&self, position: Position) -> u8 {
+        self.elevations[position.x][position.y]
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        (0..self.rows()).flat_map(move |x| (0..self.columns()).map(move |y| Position { x, y }))
+    }
+
+    fn adjacent(&self, position: Position) -> Vec<Position> {
+        let Position { x: i, y: j } = position;
+        let mut neighbours = Vec::new();
+        if i > 0 {
+            neighbours.push(Position { x: i - 1, y: j }); // Moving up
+        }
+        if i + 1 < self.rows() {
+            neighbours.push(Position { x: i + 1, y: j }); // Moving down
+        }
+        if j > 0 {
+            neighbours.push(Position { x: i, y: j - 1 }); // Moving left
+        }
+        if j + 1 < self.columns() {
+            neighbours.push(Position { x: i, y: j + 1 }); // Moving right
+        }
+        neighbours
+    }
+
+    // Can we climb directly from one position onto its neighbour?
+    pub fn can_climb(&self, from: Position, to: Position) -> bool {
+        self.elevation(to) <= self.elevation(from) + 1
+    }
+
+    // Single BFS from the end over the reversed edges, giving the distance from every cell to the end
+    pub fn distances_to_end(&self) -> DistanceMap {
+        // Reversed edges - Moving from the current node onto any neighbour that could have climbed onto it
+        let reversed = |current: &Position| {
+            self.adjacent(*current)
+                .into_iter()
+                .filter(|neighbour| self.can_climb(*neighbour, *current))
+                .map(|neighbour| (neighbour, 1))
+                .collect::<Vec<_>>()
+        };
+        DistanceMap {
+            result: search::bfs(&reversed, [self.end]),
+        }
+    }
+
+    // Starting position at the given elevation with the shortest route to the end
+    pub fn nearest_with_elevation(
+        &self,
+        distances: &DistanceMap,
+        elevation: char,
+    ) -> Option<Position> {
+        if !elevation.is_ascii_lowercase() {
+            return None;
+        }
+        self.positions()
+            .filter(|p| self.elevation(*p) == elevation as u8 - b'a')
+            .filter(|p| distances.distance(*p).is_some())
+            .min_by_key(|p| distances.distance(*p))
+    }
+
+    // Draws the route onto the map as in the puzzle text - each step is marked with the direction taken from it
+    pub fn render_route(&self, route: &[Position]) -> String {
+        let mut canvas = vec![vec!['.'; self.columns()]; self.rows()];
+        for step in route.windows(2) {
+            let (from, to) = (step[0], step[1]);
+            canvas[from.x][from.y] = match (
+                to.x as isize - from.x as isize,
+                to.y as isize - from.y as isize,
+            ) {
+                (-1, _) => '^',
+                (1, _) => 'v',
+                (_, -1) => '<',
+                _ => '>',
+            };
+        }
+        canvas[self.end.x][self.end.y] = 'E';
+
+        let mut result = String::new();
+        for row in canvas {
+            result.extend(row);
+            result.push('\n');
+        }
+        result
+    }
+
+    // Writes the distance to the end for every position as a binary netpbm (PPM) image, one pixel per position.
+    // Positions closer to the end are brighter, and the route is highlighted on top.
+    pub fn write_distance_heatmap(
+        &self,
+        distances: &DistanceMap,
+        route: &[Position],
+        path: &str,
+    ) -> std::io::Result<()> {
+        if let Some(parent) = std::path::Path::new(path).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let furthest = self
+            .positions()
+            .filter_map(|p| distances.distance(p))
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let mut pixels = vec![vec![UNREACHABLE_COLOUR; self.columns()]; self.rows()];
+        for position in self.positions() {
+            if let Some(distance) = distances.distance(position) {
+                let shade = (255 - (distance * 255 / furthest)) as u8;
+                pixels[position.x][position.y] = [shade / 4, shade, shade];
+            }
+        }
+        for position in route {
+            pixels[position.x][position.y] = ROUTE_COLOUR;
+        }
+
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        write!(writer, "P6\n{} {}\n255\n", self.columns(), self.rows())?;
+        for row in pixels {
+            for pixel in row {
+                writer.write_all(&pixel)?;
+            }
+        }
+        writer.flush()
+    }
+}
+
+pub struct DistanceMap {
+    // Searched backwards from the end, so the predecessor of each position is the following step towards the end
+    result: SearchResult<Position>,
+}
+
+impl DistanceMap {
+    pub fn distance(&self, position: Position) -> Option<u32> {
+        self.result.distance(&position)
+    }
+
+    pub fn visits(&self) -> usize {
+        self.result.visits
+    }
+
+    // Reconstructs a shortest route from the given position to the end, including both ends
+    pub fn route(&self, from: Position) -> Option<Vec<Position>> {
+        let mut route = self.result.path_to(&from)?;
+        route.reverse();
+        Some(route)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi\n";
+
+    #[test]
+    fn example() {
+        let heightmap = Heightmap::parse(EXAMPLE.as_bytes()).unwrap();
+        let distances = heightmap.distances_to_end();
+        assert_eq!(distances.distance(heightmap.start), Some(31));
+
+        let nearest = heightmap.nearest_with_elevation(&distances, 'a').unwrap();
+        assert_eq!(distances.distance(nearest), Some(29));
+        assert_eq!(distances.route(nearest).unwrap().len(), 30);
+    }
+
+    #[test]
+    fn walled_off_start() {
+        // Only the 'a' next to the climb from 'a' to 'z' can reach the end
+        let map = format!(
+            "S{}\nca{}E\n",
+            "c".repeat(27),
+            ('b'..='z').collect::<String>()
+        );
+        let heightmap = Heightmap::parse(map.as_bytes()).unwrap();
+        let distances = heightmap.distances_to_end();
+        assert_eq!(distances.distance(heightmap.start), None);
+        let nearest = heightmap.nearest_with_elevation(&distances, 'a').unwrap();
+        assert_eq!(nearest, Position { x: 1, y: 1 });
+        assert_eq!(distances.distance(nearest), Some(26));
+    }
+
+    #[test]
+    fn bad_characters() {
+        let error = Heightmap::parse("Sab\nab_E\n".as_bytes()).err().unwrap();
+        assert_eq!((error.line, error.column, error.character), (2, 3, '_'));
+        let heightmap = Heightmap::parse(EXAMPLE.as_bytes()).unwrap();
+        let distances = heightmap.distances_to_end();
+        assert_eq!(heightmap.nearest_with_elevation(&distances, 'A'), None);
+    }
+}
@@ -0,0 +1,14 @@
+// Code shared between the puzzle binaries
+pub mod cave;
+pub mod cpu;
+pub mod crane;
+pub mod crt;
+pub mod factory;
+pub mod forest;
+pub mod heightmap;
+pub mod monkey;
+pub mod packet;
+pub mod rope;
+pub mod search;
+pub mod sections;
+pub mod signal;
@@ -0,0 +1,391 @@
+// Monkeys for puzzle 11 - Throwing items to each other based on the worry levels
+pub struct Item {
+    pub worry: u128,
+}
+
+pub struct Monkey {
+    pub id: usize,
+    pub items: Vec<Item>,
+    pub items_inspected_count: u64,
+    ops: fn(&mut Item) -> (),
+    // The monkey tests whether the worry level is divisible by this
+    pub divisor: u128,
+    test: fn(&Item, u128) -> usize,
+}
+
+pub struct Present {
+    pub receiver: usize,
+    pub item: Item,
+}
+
+// How the worry level drops after every inspection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relief {
+    // Divided by the amount, rounding down
+    DivideBy(u128),
+    // No relief at all - Kept below the modulus instead, see divisor_product
+    Modulo(u128),
+}
+
+impl Monkey {
+    pub fn new(
+        id: usize,
+        starting_items: Vec<Item>,
+        ops: fn(&mut Item) -> (),
+        divisor: u128,
+        test: fn(&Item, u128) -> usize,
+    ) -> Self {
+        Monkey {
+            id,
+            items: starting_items,
+            items_inspected_count: 0,
+            ops,
+            divisor,
+            test,
+        }
+    }
+
+    fn inspect_item(&mut self, item: &mut Item) {
+        let ops_fn = self.ops;
+        ops_fn(item);
+        self.items_inspected_count += 1;
+    }
+
+    fn wrap_present(&self, item: Item) -> Present {
+        let receiver = (self.test)(&item, self.divisor);
+        Present { receiver, item }
+    }
+
+    pub fn inspect_and_throw(&mut self, relief: Relief) -> Vec<Present> {
+        let mut presents = Vec::new();
+        self.items.reverse(); // Start inspecting and throw from the start of the queue
+        while let Some(mut item) = self.items.pop() {
+            // Inspecting the item
+            self.inspect_item(&mut item);
+
+            // After Inspection , worry level decreases
+            match relief {
+                Relief::DivideBy(amount) => item.worry /= amount,
+                Relief::Modulo(modulus) => item.worry %= modulus,
+            }
+
+            // Wrapping the item as a present for the other monkeys
+            let present = self.wrap_present(item);
+            presents.push(present);
+        }
+        presents
+    }
+}
+
+// Product of the divisors the monkeys test against - keeping the worry levels below it leaves
+// every test result unchanged, and stops them from overflowing without the division by 3
+pub fn divisor_product(monkeys: &[Monkey]) -> u128 {
+    monkeys.iter().map(|monkey| monkey.divisor).product()
+}
+
+// Every monkey takes its turn once, in order
+pub fn play_round(monkeys: &mut [Monkey], relief: Relief) {
+    for i in 0..monkeys.len() {
+        let monkey_presents = monkeys[i].inspect_and_throw(relief);
+
+        for present in monkey_presents {
+            monkeys[present.receiver].items.push(present.item);
+        }
+    }
+}
+
+// Inspection counts of the monkeys, from the most active one down
+pub fn inspection_counts(monkeys: &[Monkey]) -> Vec<u64> {
+    let mut counts = monkeys
+        .iter()
+        .map(|monkey| monkey.items_inspected_count)
+        .collect::<Vec<_>>();
+    counts.sort();
+    counts.reverse();
+    counts
+}
+
+// The monkeys from the puzzle input
+pub fn input_monkeys() -> Vec<Monkey> {
+    let mut monkeys = Vec::new();
+    // Creating the individual monkeys
+    monkeys.push(Monkey::new(
+        0,
+        vec![Item { worry: 61 }],
+        |item| item.worry *= 11,
+        5,
+        |item, divisor| {
+            if item.worry.rem_euclid(divisor) == 0 {
+                7
+            } else {
+                4
+            }
+        },
+    ));
+    monkeys.push(Monkey::new(
+        1,
+        vec![
+            Item { worry: 76 },
+            Item { worry: 92 },
+            Item { worry: 53 },
+            Item { worry: 93 },
+            Item { worry: 79 },
+            Item { worry: 86 },
+            Item { worry: 81 },
+        ],
+        |item| item.worry += 4,
+        2,
+        |item, divisor| {
+            if item.worry.rem_euclid(divisor) == 0 {
+                2
+            } else {
+                6
+            }
+        },
+    ));
+    monkeys.push(Monkey::new(
+        2,
+        vec![Item { worry: 91 }, Item { worry: 99 }],
+        |item| item.worry *= 19,
+        13,
+        |item, divisor| {
+            if item.worry.rem_euclid(divisor) == 0 {
+                5
+            } else {
+                0
+            }
+        },
+    ));
+    monkeys.push(Monkey::new(
+        3,
+        vec![Item { worry: 58 }, Item { worry: 67 }, Item { worry: 66 }],
+        |item| item.worry *= item.worry,
+        7,
+        |item, divisor| {
+            if item.worry.rem_euclid(divisor) == 0 {
+                6
+            } else {
+                1
+            }
+        },
+    ));
+    monkeys.push(Monkey::new(
+        4,
+        vec![
+            Item { worry: 94 },
+            Item { worry: 54 },
+            Item { worry: 62 },
+            Item { worry: 73 },
+        ],
+        |item| item.worry += 1,
+        19,
+        |item, divisor| {
+            if item.worry.rem_euclid(divisor) == 0 {
+                3
+            } else {
+                7
+            }
+        },
+    ));
+    monkeys.push(Monkey::new(
+        5,
+        vec![
+            Item { worry: 59 },
+            Item { worry: 95 },
+            Item { worry: 51 },
+            Item { worry: 58 },
+            Item { worry: 58 },
+        ],
+        |item| item.worry += 3,
+        11,
+        |item, divisor| {
+            if item.worry.rem_euclid(divisor) == 0 {
+                0
+            } else {
+                4
+            }
+        },
+    ));
+    monkeys.push(Monkey::new(
+        6,
+        vec![
+            Item { worry: 87 },
+            Item { worry: 69 },
+            Item { worry: 92 },
+            Item { worry: 56 },
+            Item { worry: 91 },
+            Item { worry: 93 },
+            Item { worry: 88 },
+            Item { worry: 73 },
+        ],
+        |item| item.worry += 8,
+        3,
+        |item, divisor| {
+            if item.worry.rem_euclid(divisor) == 0 {
+                5
+            } else {
+                2
+            }
+        },
+    ));
+    monkeys.push(Monkey::new(
+        7,
+        vec![
+            Item { worry: 71 },
+            Item { worry: 57 },
+            Item { worry: 86 },
+            Item { worry: 67 },
+            Item { worry: 96 },
+            Item { worry: 95 },
+        ],
+        |item| item.worry += 7,
+        17,
+        |item, divisor| {
+            if item.worry.rem_euclid(divisor) == 0 {
+                3
+            } else {
+                1
+            }
+        },
+    ));
+
+    monkeys
+}
+
+// The monkeys from the puzzle's example, to compare the reports with the puzzle statement
+pub fn example_monkeys() -> Vec<Monkey> {
+    let mut monkeys = Vec::new();
+    // Creating the individual monkeys
+    monkeys.push(Monkey::new(
+        0,
+        vec![Item { worry: 79 }, Item { worry: 98 }],
+        |item| item.worry *= 19,
+        23,
+        |item, divisor| {
+            if item.worry.rem_euclid(divisor) == 0 {
+                2
+            } else {
+                3
+            }
+        },
+    ));
+    monkeys.push(Monkey::new(
+        1,
+        vec![
+            Item { worry: 54 },
+            Item { worry: 65 },
+            Item { worry: 75 },
+            Item { worry: 74 },
+        ],
+        |item| item.worry += 6,
+        19,
+        |item, divisor| {
+            if item.worry.rem_euclid(divisor) == 0 {
+                2
+            } else {
+                0
+            }
+        },
+    ));
+    monkeys.push(Monkey::new(
+        2,
+        vec![Item { worry: 79 }, Item { worry: 60 }, Item { worry: 97 }],
+        |item| item.worry *= item.worry,
+        13,
+        |item, divisor| {
+            if item.worry.rem_euclid(divisor) == 0 {
+                1
+            } else {
+                3
+            }
+        },
+    ));
+    monkeys.push(Monkey::new(
+        3,
+        vec![Item { worry: 74 }],
+        |item| item.worry += 3,
+        17,
+        |item, divisor| {
+            if item.worry.rem_euclid(divisor) == 0 {
+                0
+            } else {
+                1
+            }
+        },
+    ));
+
+    monkeys
+}
+
+// Worry levels of the items each monkey holds, in the puzzle's format
+pub fn print_items(monkeys: &[Monkey], round: u32) {
+    println!(
+        "After round {}, the monkeys are holding items with these worry levels:",
+        round
+    );
+    for monkey in monkeys {
+        let worries = monkey
+            .items
+            .iter()
+            .map(|item| item.worry.to_string())
+            .collect::<Vec<_>>();
+        println!("Monkey {}: {}", monkey.id, worries.join(", "));
+    }
+    println!();
+}
+
+pub fn print_inspections(monkeys: &[Monkey], round: u32) {
+    println!("== After round {} ==", round);
+    for monkey in monkeys {
+        println!(
+            "Monkey {} inspected items {} times.",
+            monkey.id, monkey.items_inspected_count
+        );
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monkey_business(rounds: u32, relief: impl Fn(&[Monkey]) -> Relief) -> u64 {
+        let mut monkeys = example_monkeys();
+        let relief = relief(&monkeys);
+        for _ in 0..rounds {
+            play_round(&mut monkeys, relief);
+        }
+        let counts = inspection_counts(&monkeys);
+        counts[0] * counts[1]
+    }
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(monkey_business(20, |_| Relief::DivideBy(3)), 10605);
+    }
+
+    #[test]
+    fn example_part2() {
+        let modulo = |monkeys: &[Monkey]| Relief::Modulo(divisor_product(monkeys));
+        assert_eq!(monkey_business(10000, modulo), 2713310158);
+    }
+
+    #[test]
+    fn example_items_after_round_1() {
+        let mut monkeys = example_monkeys();
+        play_round(&mut monkeys, Relief::DivideBy(3));
+        let worries = monkeys
+            .iter()
+            .map(|monkey| monkey.items.iter().map(|item| item.worry).collect())
+            .collect::<Vec<Vec<_>>>();
+        assert_eq!(
+            worries,
+            [
+                vec![20, 23, 27, 26],
+                vec![2080, 25, 167, 207, 401, 1046],
+                vec![],
+                vec![]
+            ]
+        );
+    }
+}
@@ -0,0 +1,410 @@
+// Distress signal packets for puzzle 13 - Nested lists of integers such as [1,[2,3]]
+use std::{cmp::Ordering, fmt::Debug, str::FromStr};
+
+use num_bigint::BigUint;
+
+#[derive(Clone, Eq)]
+pub enum Value {
+    Number(BigUint),
+    List(Vec<Value>),
+}
+
+impl Debug for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(arg0) => write!(f, "{arg0}"),
+            Self::List(arg0) => f.debug_list().entries(arg0.iter()).finish(),
+        }
+    }
+}
+
+// Compact formatting, as the packets are written in the puzzle input
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(arg0) => write!(f, "{arg0}"),
+            Self::List(arg0) => {
+                write!(f, "[")?;
+                for (i, value) in arg0.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+// Records a line of the comparison trace, indented by the depth of the comparison
+fn log(trace: &mut Option<Vec<String>>, depth: usize, message: impl FnOnce() -> String) {
+    if let Some(lines) = trace {
+        lines.push(format!("{}- {}", "  ".repeat(depth), message()));
+    }
+}
+
+impl Value {
+    // Compares the packets, explaining each step of the comparison in the same format as the puzzle text
+    pub fn explain(&self, other: &Self) -> (Ordering, Vec<String>) {
+        let mut trace = Some(Vec::new());
+        let ordering = self.compare(other, 0, &mut trace);
+        (ordering, trace.unwrap_or_default())
+    }
+
+    fn compare(&self, other: &Self, depth: usize, trace: &mut Option<Vec<String>>) -> Ordering {
+        log(trace, depth, || format!("Compare {} vs {}", self, other));
+        match (self, other) {
+            (Value::Number(lhs), Value::Number(rhs)) => {
+                let ordering = lhs.cmp(rhs);
+                match ordering {
+                    Ordering::Less => log(trace, depth + 1, || {
+                        "Left side is smaller, so inputs are in the right order".to_owned()
+                    }),
+                    Ordering::Greater => log(trace, depth + 1, || {
+                        "Right side is smaller, so inputs are not in the right order".to_owned()
+                    }),
+                    Ordering::Equal => {}
+                }
+                ordering
+            }
+            (Value::Number(lhs), rhs) => {
+                let num_list = Value::List(vec![Value::Number(lhs.clone())]);
+                log(trace, depth + 1, || {
+                    format!(
+                        "Mixed types; convert left to {} and retry comparison",
+                        num_list
+                    )
+                });
+                num_list.compare(rhs, depth + 1, trace)
+            }
+            (lhs, Value::Number(rhs)) => {
+                let num_list = Value::List(vec![Value::Number(rhs.clone())]);
+                log(trace, depth + 1, || {
+                    format!(
+                        "Mixed types; convert right to {} and retry comparison",
+                        num_list
+                    )
+                });
+                lhs.compare(&num_list, depth + 1, trace)
+            }
+            (Value::List(lhs), Value::List(rhs)) => {
+                for (left_val, right_val) in lhs.iter().zip(rhs.iter()) {
+                    match left_val.compare(right_val, depth + 1, trace) {
+                        Ordering::Equal => continue, // Proceed to check the next value.
+                        ord => return ord,
+                    }
+                }
+                // All the compared values are equal - The list that runs out of values first is smaller.
+                let ordering = lhs.len().cmp(&rhs.len());
+                match ordering {
+                    Ordering::Less => log(trace, depth + 1, || {
+                        "Left side ran out of items, so inputs are in the right order".to_owned()
+                    }),
+                    Ordering::Greater => log(trace, depth + 1, || {
+                        "Right side ran out of items, so inputs are not in the right order"
+                            .to_owned()
+                    }),
+                    Ordering::Equal => {}
+                }
+                ordering
+            }
+        }
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other, 0, &mut None)
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Number(lhs), Self::Number(rhs)) => lhs == rhs,
+            (Self::List(lhs), Self::List(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // 1-based column of the character where parsing failed
+    pub column: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Recursive descent parser for the packet grammar
+//   value  := number | list
+//   list   := '[' (value (',' value)*)? ']'
+//   number := [0-9]+
+// Whitespace is allowed between tokens, so the Debug output can be parsed back.
+struct Parser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Parser {
+            chars: input.chars().collect(),
+            pos: 0,
+            input,
+        }
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        let found = match self.chars.get(self.pos) {
+            Some(c) => format!("found '{}'", c),
+            None => "found end of input".to_owned(),
+        };
+        ParseError {
+            column: self.pos + 1,
+            message: format!("{}, {} in \"{}\"", message, found, self.input),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.chars.get(self.pos), Some(c) if c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.get(self.pos).copied()
+    }
+
+    fn parse_value(&mut self) -> Result<Value, ParseError> {
+        match self.peek() {
+            Some('[') => self.parse_list(),
+            Some(c) if c.is_ascii_digit() => self.parse_number(),
+            _ => Err(self.error("expected a number or '['")),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+        while matches!(self.chars.get(self.pos), Some(c) if c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let digits = self.chars[start..self.pos].iter().collect::<String>();
+        match digits.parse() {
+            Ok(number) => Ok(Value::Number(number)),
+            Err(_) => Err(self.error("expected a number")),
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<Value, ParseError> {
+        self.pos += 1; // Consuming the opening '['
+        let mut values = Vec::new();
+
+        // Empty list
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(Value::List(values));
+        }
+
+        loop {
+            values.push(self.parse_value()?);
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(Value::List(values));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+}
+
+impl FromStr for Value {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let value = parser.parse_value()?;
+        match parser.peek() {
+            None => Ok(value),
+            Some(_) => Err(parser.error("expected end of packet")),
+        }
+    }
+}
+
+// Parses a packet, panicking with the line number and the column of the failure
+pub fn parse_packet(line: &str, line_number: usize) -> Value {
+    let packet: Value = match line.parse() {
+        Ok(packet) => packet,
+        Err(e) => panic!("Failed to parse packet on line {}, {}", line_number, e),
+    };
+    // The Debug formatting should always parse back into the same packet
+    debug_assert_eq!(format!("{:?}", packet).parse::<Value>(), Ok(packet.clone()));
+    packet
+}
+
+// 1-based positions the dividers would take if they were stably sorted after being appended to the packets.
+// Each position is simply one more than the number of packets and other dividers ordered before it,
+// so only O(n) comparisons are needed per divider instead of sorting all the packets.
+// Ties are broken as the stable sort would - packets before dividers, then dividers in their given order.
+pub fn divider_positions(packets: &[Value], dividers: &[Value]) -> Vec<usize> {
+    dividers
+        .iter()
+        .enumerate()
+        .map(|(i, divider)| {
+            let packets_before = packets.iter().filter(|p| *p <= divider).count();
+            let dividers_before = dividers
+                .iter()
+                .enumerate()
+                .filter(|(j, d)| match d.cmp(&divider) {
+                    Ordering::Less => true,
+                    Ordering::Equal => *j < i,
+                    Ordering::Greater => false,
+                })
+                .count();
+            packets_before + dividers_before + 1
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+
+    fn parse_all(input: &str) -> Vec<Value> {
+        input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.parse().unwrap())
+            .collect()
+    }
+
+    // Positions found by actually sorting, as the puzzle describes
+    fn sorted_positions(packets: &[Value], dividers: &[Value]) -> Vec<usize> {
+        let mut all = packets
+            .iter()
+            .cloned()
+            .map(|p| (p, None))
+            .chain(
+                dividers
+                    .iter()
+                    .cloned()
+                    .enumerate()
+                    .map(|(i, d)| (d, Some(i))),
+            )
+            .collect::<Vec<_>>();
+        all.sort_by(|a, b| a.0.cmp(&b.0));
+        (0..dividers.len())
+            .map(|i| all.iter().position(|(_, d)| *d == Some(i)).unwrap() + 1)
+            .collect()
+    }
+
+    #[test]
+    fn example_dividers() {
+        let packets = parse_all(EXAMPLE);
+        let dividers = [parse_packet("[[2]]", 1), parse_packet("[[6]]", 2)];
+        assert_eq!(divider_positions(&packets, &dividers), vec![10, 14]);
+        assert_eq!(sorted_positions(&packets, &dividers), vec![10, 14]);
+    }
+
+    #[test]
+    fn custom_dividers() {
+        let packets = parse_all(EXAMPLE);
+        let dividers = parse_all("[[6]]\n[]\n[10]\n[1,1,4]");
+        let positions = divider_positions(&packets, &dividers);
+        assert_eq!(positions, sorted_positions(&packets, &dividers));
+        // The empty list sorts straight after the example's own [] packet, and [10] after everything
+        assert_eq!(positions[1], 2);
+        assert_eq!(positions[2], packets.len() + dividers.len());
+    }
+
+    #[test]
+    fn dividers_that_compare_equal() {
+        let packets = parse_all(EXAMPLE);
+        // [2], [[2]] and 2 all compare equal, and [1,1,3,1,1] is equal to a divider given as is
+        let dividers = parse_all("[[2]]\n[2]\n[[[2]]]\n[1,1,3,1,1]");
+        let positions = divider_positions(&packets, &dividers);
+        assert_eq!(positions, sorted_positions(&packets, &dividers));
+        assert_eq!(positions[1], positions[0] + 1);
+        assert_eq!(positions[2], positions[1] + 1);
+    }
+
+    #[test]
+    fn parse_error_columns() {
+        let error = "[1,2".parse::<Value>().unwrap_err();
+        assert_eq!(error.column, 5);
+        assert!(error.message.contains("found end of input"));
+
+        let error = "[1,,2]".parse::<Value>().unwrap_err();
+        assert_eq!(error.column, 4);
+        assert!(error.message.contains("found ','"));
+
+        assert_eq!("[1]]".parse::<Value>().unwrap_err().column, 4);
+        assert_eq!("[a]".parse::<Value>().unwrap_err().column, 2);
+    }
+
+    #[test]
+    fn parse_big_integers() {
+        let big = "123456789012345678901234567890";
+        let packet: Value = format!("[{},[1]]", big).parse().unwrap();
+        let expected = Value::List(vec![
+            Value::Number(big.parse().unwrap()),
+            Value::List(vec![Value::Number(1u32.into())]),
+        ]);
+        assert_eq!(packet, expected);
+        // Compared as integers - differing only in the last of the 30 digits
+        let next: Value = format!("[{}1]", &big[..big.len() - 1]).parse().unwrap();
+        assert!(packet < next);
+    }
+
+    #[test]
+    fn debug_round_trip() {
+        for packet in parse_all(EXAMPLE) {
+            let debug = format!("{:?}", packet);
+            assert_eq!(debug.parse::<Value>(), Ok(packet.clone()));
+            assert_eq!(format!("{}", packet).parse::<Value>(), Ok(packet));
+        }
+    }
+}
@@ -0,0 +1,261 @@
+// Rope engine for puzzle 9 - A chain of knots, each following the one in front of it
+use std::{collections::HashSet, io::BufRead};
+
+pub type Position = (i32, i32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    U,
+    D,
+    L,
+    R,
+}
+
+impl Direction {
+    pub fn delta(&self) -> Position {
+        match self {
+            Direction::U => (0, 1),
+            Direction::D => (0, -1),
+            Direction::L => (-1, 0),
+            Direction::R => (1, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Motion {
+    pub direction: Direction,
+    pub steps: u32,
+}
+
+pub fn parse_motions(reader: impl BufRead) -> Vec<Motion> {
+    let pattern = regex::Regex::new("([A-Z]{1}) ([0-9]+)").unwrap();
+    let mut motions = Vec::new();
+
+    for rline in reader.lines() {
+        let line = rline.unwrap();
+        let groups = pattern.captures(&line).unwrap();
+        let direction = match groups.get(1).unwrap().as_str() {
+            "U" => Direction::U,
+            "D" => Direction::D,
+            "L" => Direction::L,
+            "R" => Direction::R,
+            _ => panic!("Unknown direction in \"{}\"", line),
+        };
+        let steps = groups.get(2).unwrap().as_str().parse::<u32>().unwrap();
+        motions.push(Motion { direction, steps });
+    }
+
+    motions
+}
+
+// How much of the rope's movement to print
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verbosity {
+    // Only the answer
+    Quiet,
+    // The final map of the cells visited by the tail
+    VisitedMap,
+    // A frame after every step, only sensible for small inputs
+    Frames,
+}
+
+// Inclusive area drawn by the renderings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl Bounds {
+    // The knots never leave the area covered by the head, so the head's path sets the bounds
+    pub fn of_motions(motions: &[Motion]) -> Self {
+        let mut head = (0, 0);
+        let mut bounds = Bounds {
+            min: head,
+            max: head,
+        };
+        for motion in motions.iter() {
+            let (dx, dy) = motion.direction.delta();
+            head.0 += dx * motion.steps as i32;
+            head.1 += dy * motion.steps as i32;
+            bounds.include(head);
+        }
+        bounds
+    }
+
+    pub fn of_positions<'a>(positions: impl IntoIterator<Item = &'a Position>) -> Self {
+        let mut bounds = Bounds {
+            min: (0, 0),
+            max: (0, 0),
+        };
+        for position in positions {
+            bounds.include(*position);
+        }
+        bounds
+    }
+
+    fn include(&mut self, position: Position) {
+        self.min = (self.min.0.min(position.0), self.min.1.min(position.1));
+        self.max = (self.max.0.max(position.0), self.max.1.max(position.1));
+    }
+
+    // Draws the area top row first, since up is the positive y direction
+    fn render(&self, mut cell: impl FnMut(Position) -> char) -> String {
+        let mut output = String::new();
+        for y in (self.min.1..=self.max.1).rev() {
+            for x in self.min.0..=self.max.0 {
+                output.push(cell((x, y)));
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+impl std::fmt::Display for Motion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} {}", self.direction, self.steps)
+    }
+}
+
+pub struct Rope {
+    // The head is the first knot, the tail the last one
+    knots: Vec<Position>,
+    // Every position occupied by each knot, including the start
+    visited: Vec<HashSet<Position>>,
+}
+
+impl Rope {
+    pub fn new(length: usize) -> Self {
+        assert!(length > 0, "A rope needs at least one knot");
+        Rope {
+            knots: vec![(0, 0); length],
+            visited: vec![HashSet::from([(0, 0)]); length],
+        }
+    }
+
+    pub fn knots(&self) -> &[Position] {
+        &self.knots
+    }
+
+    pub fn head(&self) -> Position {
+        self.knots[0]
+    }
+
+    pub fn tail(&self) -> Position {
+        *self.knots.last().unwrap()
+    }
+
+    pub fn visited(&self, knot: usize) -> &HashSet<Position> {
+        &self.visited[knot]
+    }
+
+    pub fn tail_visited(&self) -> &HashSet<Position> {
+        self.visited.last().unwrap()
+    }
+
+    // Moves the head a single step, the rest of the knots follow
+    pub fn step(&mut self, direction: Direction) {
+        let (dx, dy) = direction.delta();
+        self.knots[0].0 += dx;
+        self.knots[0].1 += dy;
+        self.visited[0].insert(self.knots[0]);
+
+        for i in 1..self.knots.len() {
+            let front = self.knots[i - 1];
+            let back = &mut self.knots[i];
+            let (x_delta, y_delta) = (front.0 - back.0, front.1 - back.1);
+            if x_delta.abs() <= 1 && y_delta.abs() <= 1 {
+                break; // Still in contact, so none of the knots behind will move either.
+            }
+
+            // Moving a single step towards the front knot on both axes
+            back.0 += x_delta.signum();
+            back.1 += y_delta.signum();
+            self.visited[i].insert(*back);
+        }
+    }
+
+    // Applies a whole motion, calling the callback after every step
+    pub fn apply(&mut self, motion: &Motion, mut callback: impl FnMut(&Rope)) {
+        for _ in 0..motion.steps {
+            self.step(motion.direction);
+            callback(self);
+        }
+    }
+
+    // Label of the knot in the frames - H for the head, then the knot numbers (T for a two knot rope)
+    fn label(&self, knot: usize) -> char {
+        match knot {
+            0 => 'H',
+            1 if self.knots.len() == 2 => 'T',
+            _ => char::from_digit(knot as u32, 36)
+                .unwrap_or('+')
+                .to_ascii_uppercase(),
+        }
+    }
+
+    // The rope in the puzzle's notation - knots nearer the head are drawn on top, s marks the start
+    pub fn render_frame(&self, bounds: &Bounds) -> String {
+        bounds.render(
+            |position| match self.knots.iter().position(|knot| *knot == position) {
+                Some(knot) => self.label(knot),
+                None if position == (0, 0) => 's',
+                None => '.',
+            },
+        )
+    }
+
+    // The cells visited by a knot as #, with s marking the start
+    pub fn render_visited(&self, knot: usize) -> String {
+        let visited = self.visited(knot);
+        let bounds = Bounds::of_positions(visited);
+        bounds.render(|position| match position {
+            (0, 0) => 's',
+            _ if visited.contains(&position) => '#',
+            _ => '.',
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n";
+    const LARGER_EXAMPLE: &str = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20\n";
+
+    fn tail_visited_count(input: &str, length: usize) -> usize {
+        let mut rope = Rope::new(length);
+        for motion in parse_motions(input.as_bytes()).iter() {
+            rope.apply(motion, |_| {});
+        }
+        rope.tail_visited().len()
+    }
+
+    #[test]
+    fn example_two_knots() {
+        assert_eq!(tail_visited_count(EXAMPLE, 2), 13);
+    }
+
+    #[test]
+    fn example_ten_knots() {
+        assert_eq!(tail_visited_count(EXAMPLE, 10), 1);
+        assert_eq!(tail_visited_count(LARGER_EXAMPLE, 10), 36);
+    }
+
+    #[test]
+    fn single_knot_follows_the_head() {
+        let mut rope = Rope::new(1);
+        rope.apply(
+            &Motion {
+                direction: Direction::L,
+                steps: 3,
+            },
+            |_| {},
+        );
+        assert_eq!(rope.tail(), (-3, 0));
+        assert_eq!(rope.tail_visited().len(), 4);
+    }
+}
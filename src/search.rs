@@ -0,0 +1,241 @@
+// Graph searches shared between the puzzles - BFS, Dijkstra and A*
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+// Anything that can list the neighbours of a node, along with the cost of moving to each of them.
+// Closures of the form |node| -> Vec<(neighbour, cost)> implement this directly.
+pub trait Neighbours<N> {
+    fn neighbours(&self, node: &N) -> Vec<(N, u32)>;
+}
+
+impl<N, F> Neighbours<N> for F
+where
+    F: Fn(&N) -> Vec<(N, u32)>,
+{
+    fn neighbours(&self, node: &N) -> Vec<(N, u32)> {
+        self(node)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchResult<N> {
+    // Distance from the nearest starting node to every node reached. Once A* stops at the goal,
+    // only the goal's distance is known to be the shortest.
+    pub distances: HashMap<N, u32>,
+    // The node each node was first reached from, starting nodes have no predecessor
+    pub predecessors: HashMap<N, N>,
+    // Number of nodes taken off the queue and expanded
+    pub visits: usize,
+}
+
+impl<N> SearchResult<N>
+where
+    N: Clone + Eq + Hash,
+{
+    fn new() -> Self {
+        SearchResult {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            visits: 0,
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<u32> {
+        self.distances.get(node).copied()
+    }
+
+    // Reconstructs the path from a starting node to the target, including both ends
+    pub fn path_to(&self, target: &N) -> Option<Vec<N>> {
+        self.distances.get(target)?;
+        let mut path = vec![target.clone()];
+        let mut current = target;
+        while let Some(previous) = self.predecessors.get(current) {
+            path.push(previous.clone());
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// Breadth first search from all the starting nodes at once - Every edge counts as a single step
+pub fn bfs<N, G>(graph: &G, starts: impl IntoIterator<Item = N>) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if result.distances.contains_key(&start) {
+            continue;
+        }
+        result.distances.insert(start.clone(), 0);
+        queue.push_back(start);
+    }
+
+    while let Some(current) = queue.pop_front() {
+        result.visits += 1;
+        let step = result.distances[&current];
+        for (neighbour, _) in graph.neighbours(&current) {
+            // Check - Has the neighbour been reached before?
+            if result.distances.contains_key(&neighbour) {
+                continue;
+            }
+            result.distances.insert(neighbour.clone(), step + 1);
+            result
+                .predecessors
+                .insert(neighbour.clone(), current.clone());
+            queue.push_back(neighbour);
+        }
+    }
+
+    result
+}
+
+// Dijkstra's algorithm from all the starting nodes at once, over the costs given by the graph
+pub fn dijkstra<N, G>(graph: &G, starts: impl IntoIterator<Item = N>) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash + Ord,
+    G: Neighbours<N>,
+{
+    best_first(graph, starts, None, |_| 0)
+}
+
+// A* from a single start towards the goal. The heuristic must never overestimate the remaining cost,
+// otherwise the distance found to the goal may not be the shortest.
+pub fn astar<N, G>(graph: &G, start: N, goal: &N, heuristic: impl Fn(&N) -> u32) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash + Ord,
+    G: Neighbours<N>,
+{
+    best_first(graph, [start], Some(goal), heuristic)
+}
+
+// Shared implementation of Dijkstra and A* - Dijkstra is simply A* with a zero heuristic and no goal
+fn best_first<N, G>(
+    graph: &G,
+    starts: impl IntoIterator<Item = N>,
+    goal: Option<&N>,
+    heuristic: impl Fn(&N) -> u32,
+) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash + Ord,
+    G: Neighbours<N>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        result.distances.insert(start.clone(), 0);
+        queue.push(Reverse((heuristic(&start), 0, start)));
+    }
+
+    while let Some(Reverse((_, cost, current))) = queue.pop() {
+        // Skipping the stale entries, a cheaper route to this node has already been expanded
+        if cost > result.distances[&current] {
+            continue;
+        }
+        result.visits += 1;
+        if goal == Some(&current) {
+            break;
+        }
+
+        for (neighbour, edge_cost) in graph.neighbours(&current) {
+            let updated_cost = cost + edge_cost;
+            if let Some(&existing) = result.distances.get(&neighbour) {
+                if existing <= updated_cost {
+                    continue;
+                }
+            }
+            result.distances.insert(neighbour.clone(), updated_cost);
+            result
+                .predecessors
+                .insert(neighbour.clone(), current.clone());
+            queue.push(Reverse((
+                updated_cost + heuristic(&neighbour),
+                updated_cost,
+                neighbour,
+            )));
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 5x5 grid with a wall down column 2, open only in the bottom row
+    fn grid_neighbours(node: &(i32, i32)) -> Vec<((i32, i32), u32)> {
+        let (x, y) = *node;
+        [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+            .into_iter()
+            .filter(|(x, y)| (0..5).contains(x) && (0..5).contains(y))
+            .filter(|(x, y)| *x != 2 || *y == 4)
+            .map(|next| (next, 1))
+            .collect()
+    }
+
+    // A -> B costs 7 directly, or 2 + 3 through C
+    fn weighted_neighbours(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'A' => vec![('B', 7), ('C', 2)],
+            'C' => vec![('B', 3), ('D', 10)],
+            'B' => vec![('D', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_from_several_starts_takes_the_nearest() {
+        let result = bfs(&grid_neighbours, [(0, 0), (4, 0)]);
+        assert_eq!(result.distance(&(0, 0)), Some(0));
+        assert_eq!(result.distance(&(4, 0)), Some(0));
+        assert_eq!(result.distance(&(1, 2)), Some(3));
+        assert_eq!(result.distance(&(3, 2)), Some(3));
+        // Around the wall through the bottom row
+        assert_eq!(result.distance(&(2, 4)), Some(6));
+        assert_eq!(result.distance(&(2, 0)), None);
+        assert_eq!(result.distances.len(), 21);
+    }
+
+    #[test]
+    fn dijkstra_follows_the_cheapest_edges() {
+        let result = dijkstra(&weighted_neighbours, ['A']);
+        assert_eq!(result.distance(&'B'), Some(5));
+        assert_eq!(result.distance(&'C'), Some(2));
+        assert_eq!(result.distance(&'D'), Some(6));
+        assert_eq!(result.path_to(&'D'), Some(vec!['A', 'C', 'B', 'D']));
+    }
+
+    #[test]
+    fn astar_reaches_the_goal() {
+        let goal = (4, 0);
+        let manhattan = |node: &(i32, i32)| node.0.abs_diff(goal.0) + node.1.abs_diff(goal.1);
+        let result = astar(&grid_neighbours, (0, 0), &goal, manhattan);
+        assert_eq!(result.distance(&goal), Some(12));
+        assert_eq!(
+            result.distance(&goal),
+            bfs(&grid_neighbours, [(0, 0)]).distance(&goal)
+        );
+    }
+
+    #[test]
+    fn path_reconstruction() {
+        let result = bfs(&grid_neighbours, [(0, 0)]);
+        let path = result.path_to(&(4, 0)).unwrap();
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(4, 0)));
+        assert_eq!(path.len() as u32, result.distance(&(4, 0)).unwrap() + 1);
+        // Every step moves to a neighbour, and the path never crosses the wall
+        for step in path.windows(2) {
+            assert!(grid_neighbours(&step[0]).iter().any(|(n, _)| *n == step[1]));
+        }
+        assert_eq!(result.path_to(&(0, 0)), Some(vec![(0, 0)]));
+        assert_eq!(result.path_to(&(2, 0)), None);
+    }
+}
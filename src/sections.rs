@@ -0,0 +1,248 @@
+// Section assignments for puzzle 4 - Inclusive ranges of section IDs, assigned to pairs of elves
+use std::{io::BufRead, str::FromStr};
+
+// Always holds at least one section - the fields are private so that start <= end holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SectionRange {
+    start: u32,
+    end: u32,
+}
+
+impl SectionRange {
+    pub fn new(start: u32, end: u32) -> Option<Self> {
+        match start <= end {
+            true => Some(SectionRange { start, end }),
+            false => None,
+        }
+    }
+
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    // Number of sections in the range - u64, as 0 to u32::MAX holds one more section than a u32 can count
+    pub fn section_count(&self) -> u64 {
+        (self.end - self.start) as u64 + 1
+    }
+
+    pub fn contains_section(&self, section: u32) -> bool {
+        (self.start..=self.end).contains(&section)
+    }
+
+    // Whether every section of the other range is also in this one
+    pub fn contains(&self, other: &SectionRange) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &SectionRange) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &SectionRange) -> Option<SectionRange> {
+        SectionRange::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    // None when the ranges have a gap between them, as the union would not be a single range
+    pub fn union(&self, other: &SectionRange) -> Option<SectionRange> {
+        let touching =
+            self.start <= other.end.saturating_add(1) && other.start <= self.end.saturating_add(1);
+        match touching {
+            true => SectionRange::new(self.start.min(other.start), self.end.max(other.end)),
+            false => None,
+        }
+    }
+
+    pub fn sections(&self) -> impl Iterator<Item = u32> {
+        self.start..=self.end
+    }
+}
+
+impl std::fmt::Display for SectionRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError(pub String);
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl FromStr for SectionRange {
+    type Err = ParseError;
+
+    // Parses ranges like "2-4"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |reason: &str| ParseError(format!("Invalid range \"{}\" - {}", s, reason));
+        let (start, end) = s
+            .trim()
+            .split_once('-')
+            .ok_or_else(|| error("missing '-'"))?;
+        let start = start.parse::<u32>().map_err(|_| error("bad start"))?;
+        let end = end.parse::<u32>().map_err(|_| error("bad end"))?;
+        SectionRange::new(start, end).ok_or_else(|| error("start is after the end"))
+    }
+}
+
+// The pair of ranges assigned to two elves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Assignment {
+    pub first: SectionRange,
+    pub second: SectionRange,
+}
+
+impl Assignment {
+    // Whether one of the elves' ranges contains the other's
+    pub fn fully_contained(&self) -> bool {
+        self.first.contains(&self.second) || self.second.contains(&self.first)
+    }
+
+    pub fn overlapping(&self) -> bool {
+        self.first.overlaps(&self.second)
+    }
+}
+
+impl std::fmt::Display for Assignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.first, self.second)
+    }
+}
+
+impl FromStr for Assignment {
+    type Err = ParseError;
+
+    // Parses pairs like "2-4,6-8"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, second) = s
+            .split_once(',')
+            .ok_or_else(|| ParseError(format!("Invalid assignment \"{}\" - missing ','", s)))?;
+        Ok(Assignment {
+            first: first.parse()?,
+            second: second.parse()?,
+        })
+    }
+}
+
+pub fn parse_assignments(reader: impl BufRead) -> Result<Vec<Assignment>, ParseError> {
+    reader
+        .lines()
+        .map(|line| line.unwrap())
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.parse())
+        .collect()
+}
+
+// Answers to both parts - pairs where one range contains the other, and pairs that overlap at all
+pub fn count_pairs(assignments: &[Assignment]) -> (usize, usize) {
+    let contained = assignments.iter().filter(|a| a.fully_contained()).count();
+    let overlapping = assignments.iter().filter(|a| a.overlapping()).count();
+    (contained, overlapping)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    // Checks every operation on the pair against the same operation on sets of section IDs
+    fn check_against_sets(assignment: &Assignment) {
+        let (a, b) = (assignment.first, assignment.second);
+        let set_a = a.sections().collect::<HashSet<_>>();
+        let set_b = b.sections().collect::<HashSet<_>>();
+        let as_set = |range: Option<SectionRange>| {
+            range.map_or_else(HashSet::new, |range| {
+                range.sections().collect::<HashSet<_>>()
+            })
+        };
+
+        assert_eq!(
+            a.contains(&b),
+            set_b.is_subset(&set_a),
+            "{} contains",
+            assignment
+        );
+        assert_eq!(
+            b.contains(&a),
+            set_a.is_subset(&set_b),
+            "{} contains",
+            assignment
+        );
+        assert_eq!(
+            a.overlaps(&b),
+            !set_a.is_disjoint(&set_b),
+            "{} overlaps",
+            assignment
+        );
+        assert_eq!(
+            a.section_count() as usize,
+            set_a.len(),
+            "{} section count",
+            assignment
+        );
+
+        let intersection = set_a.intersection(&set_b).copied().collect::<HashSet<_>>();
+        assert_eq!(
+            as_set(a.intersection(&b)),
+            intersection,
+            "{} intersection",
+            assignment
+        );
+
+        // The union is only a range when the sets leave no gap between their lowest and highest sections
+        let union = set_a.union(&set_b).copied().collect::<HashSet<_>>();
+        let lowest = *union.iter().min().unwrap();
+        let highest = *union.iter().max().unwrap();
+        match union.len() as u32 == highest - lowest + 1 {
+            true => assert_eq!(as_set(a.union(&b)), union, "{} union", assignment),
+            false => assert_eq!(a.union(&b), None, "{} union", assignment),
+        }
+    }
+
+    // Runs the set-based checks on every pair of ranges within sections 1 to max
+    fn check_all_pairs(max: u32) {
+        let ranges = (1..=max)
+            .flat_map(|start| (start..=max).map(move |end| SectionRange { start, end }))
+            .collect::<Vec<_>>();
+        for first in ranges.iter() {
+            for second in ranges.iter() {
+                check_against_sets(&Assignment {
+                    first: *first,
+                    second: *second,
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn all_small_pairs_match_sets() {
+        check_all_pairs(12);
+    }
+
+    #[test]
+    fn example_pairs() {
+        let example = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+        let assignments = parse_assignments(example.as_bytes()).unwrap();
+        assignments.iter().for_each(check_against_sets);
+        assert_eq!(count_pairs(&assignments), (2, 4));
+    }
+
+    #[test]
+    fn full_range_section_count() {
+        let full = SectionRange::new(0, u32::MAX).unwrap();
+        assert_eq!(full.section_count(), u32::MAX as u64 + 1);
+        assert_eq!(full.union(&full), Some(full));
+        assert_eq!(SectionRange::new(5, 4), None);
+        assert!("5-4".parse::<SectionRange>().is_err());
+    }
+}
@@ -0,0 +1,149 @@
+// Marker detection for puzzle 6 - Finding the first window of distinct characters in a signal
+use std::io::{BufReader, ErrorKind, Read};
+
+pub const START_OF_PACKET: usize = 4;
+pub const START_OF_MESSAGE: usize = 14;
+
+// Slides a window over the signal one character at a time, in constant time per character.
+// Counts how often each letter appears in the window - a letter's bit in the mask is set while
+// its count is non-zero, so the window is a marker once the mask has a bit for every character.
+pub struct MarkerDetector {
+    size: usize,
+    // The window as a ring buffer, indexed by the number of characters seen so far
+    window: Vec<u8>,
+    counts: [u32; 26],
+    mask: u32,
+    seen: usize,
+    marker: Option<usize>,
+}
+
+impl MarkerDetector {
+    pub fn new(size: usize) -> Self {
+        assert!(
+            (1..=26).contains(&size),
+            "A marker has between 1 and 26 distinct letters"
+        );
+        MarkerDetector {
+            size,
+            window: vec![0; size],
+            counts: [0; 26],
+            mask: 0,
+            seen: 0,
+            marker: None,
+        }
+    }
+
+    // Position of the first marker - the number of characters up to and including it
+    pub fn marker(&self) -> Option<usize> {
+        self.marker
+    }
+
+    // Feeds the next letter (0 to 25) of the signal, returning the marker once found
+    pub fn push(&mut self, letter: u8) -> Option<usize> {
+        if self.marker.is_some() {
+            return self.marker;
+        }
+
+        let slot = self.seen % self.size;
+        if self.seen >= self.size {
+            let old = self.window[slot] as usize;
+            self.counts[old] -= 1;
+            if self.counts[old] == 0 {
+                self.mask &= !(1 << old);
+            }
+        }
+
+        self.window[slot] = letter;
+        self.counts[letter as usize] += 1;
+        self.mask |= 1 << letter;
+        self.seen += 1;
+
+        if self.mask.count_ones() as usize == self.size {
+            self.marker = Some(self.seen);
+        }
+        self.marker
+    }
+}
+
+// Streams the signal once, finding the first marker for each of the window sizes.
+// Stops reading as soon as every marker has been found. Only trailing whitespace is allowed, as
+// skipping it inside the signal would shift the positions of the markers.
+pub fn find_markers(reader: impl Read, sizes: &[usize]) -> std::io::Result<Vec<Option<usize>>> {
+    let mut detectors = sizes
+        .iter()
+        .map(|size| MarkerDetector::new(*size))
+        .collect::<Vec<_>>();
+    let mut trailing = false;
+
+    for byte in BufReader::new(reader).bytes() {
+        let byte = byte?;
+        if byte.is_ascii_whitespace() {
+            trailing = true;
+            continue;
+        }
+        if trailing || !byte.is_ascii_lowercase() {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                match trailing {
+                    true => "Whitespace inside the signal".to_string(),
+                    false => format!("Unexpected character '{}' in the signal", byte as char),
+                },
+            ));
+        }
+
+        let mut done = true;
+        for detector in detectors.iter_mut() {
+            done &= detector.push(byte - b'a').is_some();
+        }
+        if done {
+            break;
+        }
+    }
+
+    Ok(detectors.iter().map(|detector| detector.marker()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The examples from the puzzle statement, with their start-of-packet and start-of-message markers
+    const EXAMPLES: &[(&str, usize, usize)] = &[
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn examples() {
+        for (signal, packet, message) in EXAMPLES.iter() {
+            let markers = find_markers(signal.as_bytes(), &[START_OF_PACKET, START_OF_MESSAGE]);
+            assert_eq!(
+                markers.unwrap(),
+                [Some(*packet), Some(*message)],
+                "{}",
+                signal
+            );
+        }
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        let markers = find_markers("abcabc\n\n".as_bytes(), &[3, 4]).unwrap();
+        assert_eq!(markers, [Some(3), None]);
+    }
+
+    #[test]
+    fn whitespace_inside_the_signal() {
+        let error = find_markers("aab\nc".as_bytes(), &[3]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn unexpected_character() {
+        let error = find_markers("abA".as_bytes(), &[4]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+}